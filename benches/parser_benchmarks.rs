@@ -1,5 +1,8 @@
 use criterion::{Criterion, black_box, criterion_group, criterion_main};
-use rebel::parse::{Collector, Parser, WordKind};
+use rebel::date::{Date, Time};
+use rebel::money::Money;
+use rebel::parse::{Collector, Parser, PathKind, WordKind};
+use rebel::tuple::{Pair, Tuple};
 
 // Simple no-op collector for benchmarking
 struct BenchCollector {
@@ -25,12 +28,67 @@ impl Collector for BenchCollector {
         Ok(())
     }
 
-    fn integer(&mut self, _: i32) -> Result<(), Self::Error> {
+    fn integer(&mut self, _: i64) -> Result<(), Self::Error> {
         self.count += 1;
         Ok(())
     }
 
-    fn float(&mut self, _: f32) -> Result<(), Self::Error> {
+    fn float(&mut self, _: f64) -> Result<(), Self::Error> {
+        self.count += 1;
+        Ok(())
+    }
+
+    fn money(&mut self, _: Money) -> Result<(), Self::Error> {
+        self.count += 1;
+        Ok(())
+    }
+
+    fn binary(&mut self, _: &[u8]) -> Result<(), Self::Error> {
+        self.count += 1;
+        Ok(())
+    }
+
+    fn date(&mut self, _: Date) -> Result<(), Self::Error> {
+        self.count += 1;
+        Ok(())
+    }
+
+    fn time(&mut self, _: Time) -> Result<(), Self::Error> {
+        self.count += 1;
+        Ok(())
+    }
+
+    fn tuple(&mut self, _: Tuple) -> Result<(), Self::Error> {
+        self.count += 1;
+        Ok(())
+    }
+
+    fn pair(&mut self, _: Pair) -> Result<(), Self::Error> {
+        self.count += 1;
+        Ok(())
+    }
+
+    fn file(&mut self, _: &str) -> Result<(), Self::Error> {
+        self.count += 1;
+        Ok(())
+    }
+
+    fn url(&mut self, _: &str) -> Result<(), Self::Error> {
+        self.count += 1;
+        Ok(())
+    }
+
+    fn email(&mut self, _: &str) -> Result<(), Self::Error> {
+        self.count += 1;
+        Ok(())
+    }
+
+    fn issue(&mut self, _: &str) -> Result<(), Self::Error> {
+        self.count += 1;
+        Ok(())
+    }
+
+    fn char(&mut self, _: char) -> Result<(), Self::Error> {
         self.count += 1;
         Ok(())
     }
@@ -50,7 +108,17 @@ impl Collector for BenchCollector {
         Ok(())
     }
 
-    fn end_path(&mut self) -> Result<(), Self::Error> {
+    fn end_path(&mut self, _: PathKind) -> Result<(), Self::Error> {
+        self.count += 1;
+        Ok(())
+    }
+
+    fn begin_paren(&mut self) -> Result<(), Self::Error> {
+        self.count += 1;
+        Ok(())
+    }
+
+    fn end_paren(&mut self) -> Result<(), Self::Error> {
         self.count += 1;
         Ok(())
     }
@@ -0,0 +1,372 @@
+// Rebel™ © 2025 Huly Labs • https://hulylabs.com • SPDX-License-Identifier: MIT
+
+//! Structural equality, ordering and hashing of values
+//!
+//! `Value` derives `PartialEq` on its raw type and data words, so two equal
+//! strings at different addresses compare unequal. The functions here look
+//! into the heap instead:
+//! - integers, floats and money compare numerically with each other, money
+//!   exactly with integers and money, and integers exactly with floats
+//! - dates compare their instants in UTC, a date without a time at midnight
+//!   and without a zone in UTC; times compare their durations
+//! - tuples compare their items, ignoring trailing zeros; pairs compare x,
+//!   then y
+//! - characters compare their code points
+//! - strings, files, URLs, emails and issues compare their text, binaries
+//!   their bytes, blocks, parens and paths their items, recursively, from
+//!   their positions
+//! - words compare their symbols, and only equal words of the same kind
+//! - other values compare their type and data words
+//!
+//! Values of different types are ordered by type: none, logic, numbers,
+//! times, dates, tuples, pairs, characters, strings, files, URLs, emails,
+//! issues, binaries, words, set-words, get-words, lit-words, blocks,
+//! paths, lit-paths, get-paths, set-paths, parens, natives, functions.
+//! `hash_value` is consistent with `equal`.
+
+use crate::mem::{Memory, MemoryError, Series, Type, Value};
+use crate::money::Money;
+use std::cmp::Ordering;
+use std::hash::{Hash, Hasher};
+
+/// Blocks nested deeper are hashed by length only
+const HASH_DEPTH: usize = 4;
+
+/// Position of a type in the ordering, shared by integers, floats and money
+fn rank(kind: Type) -> Type {
+    match kind {
+        Value::NONE => 0,
+        Value::BOOL => 1,
+        Value::INT | Value::FLOAT | Value::MONEY => 2,
+        Value::TIME => 3,
+        Value::DATE => 4,
+        Value::TUPLE => 5,
+        Value::PAIR => 6,
+        Value::CHAR => 7,
+        Value::STRING => 8,
+        Value::FILE => 9,
+        Value::URL => 10,
+        Value::EMAIL => 11,
+        Value::ISSUE => 12,
+        Value::BINARY => 13,
+        Value::WORD => 14,
+        Value::SET_WORD => 15,
+        Value::GET_WORD => 16,
+        Value::LIT_WORD => 17,
+        Value::BLOCK => 18,
+        Value::PATH => 19,
+        Value::LIT_PATH => 20,
+        Value::GET_PATH => 21,
+        Value::SET_PATH => 22,
+        Value::PAREN => 23,
+        Value::NATIVE_FUNC => 24,
+        Value::FUNC => 25,
+        kind => 26 + kind,
+    }
+}
+
+fn number(value: Value) -> Result<f64, MemoryError> {
+    match value.kind() {
+        Value::INT => value.as_int().map(|int| int as f64),
+        Value::MONEY => value.as_money().map(|money| money.to_float()),
+        _ => value.as_float(),
+    }
+}
+
+/// Integer or money amount in ten-thousandths, for exact comparison
+fn units(value: Value) -> Result<i128, MemoryError> {
+    match value.kind() {
+        Value::INT => Ok(value.as_int()? as i128 * Money::SCALE as i128),
+        _ => Ok(value.as_money()?.units() as i128),
+    }
+}
+
+/// Orders an integer or money amount against a float. The whole parts
+/// compare as integers, so an integer is not rounded to the nearest float
+/// and equality stays transitive beyond 2^53; only the fraction of money
+/// compares as a float.
+fn compare_float(a: Value, float: f64) -> Result<Ordering, MemoryError> {
+    // NaN follows every other number
+    if float.is_nan() {
+        return Ok(Ordering::Less);
+    }
+    // integers and amounts lie within ±2^63, and so do the floors below
+    let bound = 2f64.powi(63);
+    if float >= bound {
+        return Ok(Ordering::Less);
+    }
+    if float < -bound {
+        return Ok(Ordering::Greater);
+    }
+    let units = units(a)?;
+    let scale = Money::SCALE as i128;
+    let floor = float.floor();
+    let by_whole = units.div_euclid(scale).cmp(&(floor as i128));
+    if by_whole != Ordering::Equal {
+        return Ok(by_whole);
+    }
+    let fraction = units.rem_euclid(scale) as f64 / scale as f64;
+    Ok(fraction.total_cmp(&(float - floor)))
+}
+
+impl Memory {
+    /// Returns true if the values are structurally equal.
+    pub fn equal(&self, a: Value, b: Value) -> Result<bool, MemoryError> {
+        Ok(self.compare(a, b)? == Ordering::Equal)
+    }
+
+    /// Orders values structurally, consistently with `equal`.
+    pub fn compare(&self, a: Value, b: Value) -> Result<Ordering, MemoryError> {
+        self.compare_in(a, b, &mut Vec::new())
+    }
+
+    /// Orders two numbers, or two values of the same type, like `compare`.
+    /// Values of other types do not compare.
+    pub fn compare_like(&self, a: Value, b: Value) -> Result<Ordering, MemoryError> {
+        if rank(a.kind()) != rank(b.kind()) {
+            return Err(MemoryError::TypeMismatch);
+        }
+        self.compare(a, b)
+    }
+
+    /// `pending` holds the pairs of blocks being compared, a block that
+    /// contains itself compares equal where the comparison loops.
+    fn compare_in(
+        &self,
+        a: Value,
+        b: Value,
+        pending: &mut Vec<(Value, Value)>,
+    ) -> Result<Ordering, MemoryError> {
+        let by_rank = rank(a.kind()).cmp(&rank(b.kind()));
+        if by_rank != Ordering::Equal {
+            return Ok(by_rank);
+        }
+        match a.kind() {
+            Value::INT if b.is_int() => Ok(a.as_int()?.cmp(&b.as_int()?)),
+            Value::INT | Value::MONEY if !a.is_float() && !b.is_float() => {
+                Ok(units(a)?.cmp(&units(b)?))
+            }
+            Value::INT | Value::MONEY if b.is_float() => compare_float(a, b.as_float()?),
+            Value::FLOAT if !b.is_float() => Ok(compare_float(b, a.as_float()?)?.reverse()),
+            Value::INT | Value::FLOAT | Value::MONEY => {
+                let (x, y) = (number(a)?, number(b)?);
+                // NaN equals itself and follows every other number
+                Ok(x.partial_cmp(&y)
+                    .unwrap_or_else(|| x.is_nan().cmp(&y.is_nan())))
+            }
+            Value::TIME => Ok(a.as_time()?.cmp(&b.as_time()?)),
+            Value::DATE => Ok(a.as_date()?.utc_millis().cmp(&b.as_date()?.utc_millis())),
+            Value::TUPLE => {
+                let (x, y) = (a.as_tuple()?, b.as_tuple()?);
+                Ok(x.significant().cmp(y.significant()))
+            }
+            Value::PAIR => Ok(a.as_pair()?.cmp(&b.as_pair()?)),
+            Value::STRING | Value::FILE | Value::URL | Value::EMAIL | Value::ISSUE => {
+                Ok(self.string_at(a)?.cmp(self.string_at(b)?))
+            }
+            Value::BINARY => Ok(self.bytes_at(a)?.cmp(self.bytes_at(b)?)),
+            Value::WORD | Value::SET_WORD | Value::GET_WORD | Value::LIT_WORD => {
+                if a.data() == b.data() {
+                    Ok(Ordering::Equal)
+                } else {
+                    let x = self.get_string(Series::new(a.data()))?;
+                    Ok(x.cmp(self.get_string(Series::new(b.data()))?))
+                }
+            }
+            _ if a.is_any_block() => {
+                if pending.contains(&(a, b)) {
+                    return Ok(Ordering::Equal);
+                }
+                pending.push((a, b));
+                let (xs, ys) = (self.items_at(a)?, self.items_at(b)?);
+                for (x, y) in xs.iter().zip(ys) {
+                    let ordering = self.compare_in(*x, *y, pending)?;
+                    if ordering != Ordering::Equal {
+                        pending.pop();
+                        return Ok(ordering);
+                    }
+                }
+                pending.pop();
+                Ok(xs.len().cmp(&ys.len()))
+            }
+            _ => Ok((a.kind(), a.data()).cmp(&(b.kind(), b.data()))),
+        }
+    }
+
+    /// Feeds a structural hash of the value to `state`: values that are
+    /// `equal` hash the same.
+    pub fn hash_value<H: Hasher>(&self, value: Value, state: &mut H) -> Result<(), MemoryError> {
+        self.hash_in(value, state, 0)
+    }
+
+    fn hash_in<H: Hasher>(
+        &self,
+        value: Value,
+        state: &mut H,
+        depth: usize,
+    ) -> Result<(), MemoryError> {
+        rank(value.kind()).hash(state);
+        match value.kind() {
+            Value::INT | Value::FLOAT | Value::MONEY => {
+                let number = number(value)?;
+                // -0.0 equals 0.0, and all NaNs are equal
+                let bits = if number == 0.0 {
+                    0
+                } else if number.is_nan() {
+                    f64::NAN.to_bits()
+                } else {
+                    number.to_bits()
+                };
+                bits.hash(state);
+            }
+            Value::TIME => value.as_time()?.hash(state),
+            Value::DATE => value.as_date()?.utc_millis().hash(state),
+            Value::TUPLE => value.as_tuple()?.significant().hash(state),
+            Value::PAIR => value.as_pair()?.hash(state),
+            Value::STRING | Value::FILE | Value::URL | Value::EMAIL | Value::ISSUE => {
+                self.string_at(value)?.hash(state)
+            }
+            Value::BINARY => self.bytes_at(value)?.hash(state),
+            _ if value.is_any_block() => {
+                let items = self.items_at(value)?;
+                items.len().hash(state);
+                if depth < HASH_DEPTH {
+                    for item in items {
+                        self.hash_in(*item, state, depth + 1)?;
+                    }
+                }
+            }
+            // words are equal when their interned symbols are
+            _ => (value.kind(), value.data()).hash(state),
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::date::{Date, Time};
+    use crate::tuple::{Pair, Tuple};
+    use std::collections::hash_map::DefaultHasher;
+
+    fn hash(memory: &Memory, value: Value) -> Result<u64, MemoryError> {
+        let mut hasher = DefaultHasher::new();
+        memory.hash_value(value, &mut hasher)?;
+        Ok(hasher.finish())
+    }
+
+    #[test]
+    fn test_structural_equality() -> Result<(), MemoryError> {
+        let mut memory = Memory::new(65536)?;
+        let a = Value::string(memory.alloc_string("abc")?);
+        let b = Value::string(memory.alloc_string("abc")?);
+        let c = Value::string(memory.alloc_string("xabc")?);
+        assert_ne!(a, b);
+        assert!(memory.equal(a, b)?);
+        assert!(memory.equal(a, memory.next(c)?)?);
+        assert!(memory.equal(Value::int(2), Value::float(2.0))?);
+        assert_eq!(
+            hash(&memory, Value::int(2))?,
+            hash(&memory, Value::float(2.0))?
+        );
+
+        let x = Value::block(memory.alloc_items(&[Value::int(1), a])?);
+        let y = Value::block(memory.alloc_items(&[Value::float(1.0), b])?);
+        let z = Value::path(memory.alloc_items(&[Value::int(1), a])?);
+        assert!(memory.equal(x, y)?);
+        assert!(!memory.equal(x, z)?);
+        assert_eq!(hash(&memory, x)?, hash(&memory, y)?);
+
+        // a block containing itself
+        let series = memory.alloc::<Value>(1)?;
+        memory.push(series, Value::block(series))?;
+        let other = memory.alloc_items(&[Value::block(series)])?;
+        assert!(memory.equal(Value::block(series), Value::block(other))?);
+        hash(&memory, Value::block(series))?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_structural_ordering() -> Result<(), MemoryError> {
+        let mut memory = Memory::new(65536)?;
+        let abc = Value::string(memory.alloc_string("abc")?);
+        let abd = Value::string(memory.alloc_string("abd")?);
+        let short = Value::block(memory.alloc_items(&[Value::int(1)])?);
+        let long = Value::block(memory.alloc_items(&[Value::int(1), Value::int(0)])?);
+        let values = [
+            Value::none(),
+            Value::bool(true),
+            Value::int(-1),
+            Value::float(0.5),
+            Value::int(1),
+            Value::time(Time::from_nanos(-1)),
+            Value::time(Time::from_nanos(Time::HOUR)),
+            Value::date("1-Jan-2000".parse().unwrap()),
+            Value::date("1-Jan-2000/1:00".parse().unwrap()),
+            Value::tuple("1.2.3".parse().unwrap()),
+            Value::tuple("1.10.0".parse().unwrap()),
+            Value::pair(Pair::new(1, 2)),
+            Value::pair(Pair::new(2, 1)),
+            Value::char('A'),
+            Value::char('a'),
+            abc,
+            abd,
+            short,
+            long,
+        ];
+        for pair in values.windows(2) {
+            assert_eq!(memory.compare(pair[0], pair[1])?, Ordering::Less);
+            assert_eq!(memory.compare(pair[1], pair[0])?, Ordering::Greater);
+        }
+        let nan = Value::float(f64::NAN);
+        assert_eq!(memory.compare(Value::int(i64::MAX), nan)?, Ordering::Less);
+        assert!(memory.equal(nan, nan)?);
+
+        // integers beyond 2^53 are not rounded to the nearest float
+        let float = Value::float(2f64.powi(53));
+        assert!(memory.equal(Value::int(1 << 53), float)?);
+        assert!(!memory.equal(Value::int((1 << 53) + 1), float)?);
+        assert_eq!(
+            memory.compare(float, Value::int((1 << 53) + 1))?,
+            Ordering::Less
+        );
+        let float = Value::float(2f64.powi(63));
+        assert_eq!(memory.compare(Value::int(i64::MAX), float)?, Ordering::Less);
+        assert!(memory.equal(Value::int(i64::MIN), Value::float(-(2f64.powi(63))))?);
+        assert_eq!(
+            memory.compare(Value::int(-2), Value::float(-1.5))?,
+            Ordering::Less
+        );
+
+        let cent = Value::money(Money::from_units(100));
+        assert!(memory.equal(cent, Value::float(0.01))?);
+        assert!(memory.equal(Value::money(Money::from_units(20000)), Value::int(2))?);
+        assert_eq!(memory.compare(cent, Value::int(1))?, Ordering::Less);
+        assert_eq!(
+            hash(&memory, Value::money(Money::from_units(20000)))?,
+            hash(&memory, Value::int(2))?
+        );
+
+        // the same instant in two zones
+        let utc: Date = "16-Oct-2026/8:30Z".parse().unwrap();
+        let local: Date = "16-Oct-2026/10:30+2:00".parse().unwrap();
+        assert!(memory.equal(Value::date(utc), Value::date(local))?);
+        assert_eq!(
+            hash(&memory, Value::date(utc))?,
+            hash(&memory, Value::date(local))?
+        );
+        let short: Tuple = "1.2.0".parse().unwrap();
+        let long: Tuple = "1.2.0.0".parse().unwrap();
+        assert!(memory.equal(Value::tuple(short), Value::tuple(long))?);
+        assert_eq!(
+            hash(&memory, Value::tuple(short))?,
+            hash(&memory, Value::tuple(long))?
+        );
+        assert!(matches!(
+            memory.compare_like(Value::time(Time::default()), Value::int(0)),
+            Err(MemoryError::TypeMismatch)
+        ));
+        Ok(())
+    }
+}
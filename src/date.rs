@@ -0,0 +1,628 @@
+// Rebel™ © 2025 Huly Labs • https://hulylabs.com • SPDX-License-Identifier: MIT
+
+//! Calendar dates and times for the date and time types
+//!
+//! A [`Time`] is a signed count of nanoseconds, used both for times of day
+//! (`10:30:15.5`) and for durations (`-1:30`, `100:00`). A [`Date`] is a
+//! day of the proleptic Gregorian calendar, with an optional time of day
+//! kept to the millisecond and an optional time zone offset kept to the
+//! quarter hour (`16-Oct-2026/10:30:00+2:00`). A date packs into the 64
+//! bits of a value:
+//!
+//! ```text
+//! 63         36 35            9   8      7      6     0
+//! +------------+---------------+------+------+--------+
+//! | days (i28) | millis of day | time | zone | zone/15|
+//! +------------+---------------+------+------+--------+
+//! ```
+
+use std::fmt;
+use std::str::FromStr;
+use thiserror::Error;
+
+#[derive(Debug, Error, PartialEq)]
+pub enum DateError {
+    #[error("invalid date or time")]
+    Invalid,
+    #[error("date or time out of range")]
+    OutOfRange,
+}
+
+const MONTHS: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+
+/// Parses 1 to `max` ASCII digits
+fn digits(s: &str, max: usize) -> Result<i64, DateError> {
+    if s.is_empty() || s.len() > max || !s.bytes().all(|byte| byte.is_ascii_digit()) {
+        return Err(DateError::Invalid);
+    }
+    s.parse().map_err(|_| DateError::OutOfRange)
+}
+
+/// A signed duration in nanoseconds, also used as a time of day.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Time(i64);
+
+impl Time {
+    pub const SECOND: i64 = 1_000_000_000;
+    pub const MINUTE: i64 = 60 * Self::SECOND;
+    pub const HOUR: i64 = 60 * Self::MINUTE;
+    pub const DAY: i64 = 24 * Self::HOUR;
+
+    pub fn from_nanos(nanos: i64) -> Self {
+        Self(nanos)
+    }
+
+    pub fn nanos(&self) -> i64 {
+        self.0
+    }
+
+    /// Converts a number of seconds, rounding to the nanosecond
+    pub fn from_seconds(seconds: f64) -> Result<Self, DateError> {
+        Self(Self::SECOND).scale(seconds)
+    }
+
+    /// Whole hours, negative for negative times
+    pub fn hour(&self) -> i64 {
+        self.0 / Self::HOUR
+    }
+
+    pub fn minute(&self) -> i64 {
+        self.0.abs() % Self::HOUR / Self::MINUTE
+    }
+
+    /// Seconds past the minute, with their fraction
+    pub fn second(&self) -> f64 {
+        (self.0.abs() % Self::MINUTE) as f64 / Self::SECOND as f64
+    }
+
+    pub fn checked_add(self, other: Time) -> Result<Self, DateError> {
+        self.0
+            .checked_add(other.0)
+            .map(Self)
+            .ok_or(DateError::OutOfRange)
+    }
+
+    pub fn checked_sub(self, other: Time) -> Result<Self, DateError> {
+        self.0
+            .checked_sub(other.0)
+            .map(Self)
+            .ok_or(DateError::OutOfRange)
+    }
+
+    /// Scales the time by `factor`, rounding to the nanosecond
+    pub fn scale(self, factor: f64) -> Result<Self, DateError> {
+        let nanos = (self.0 as f64 * factor).round();
+        if nanos.is_finite() && nanos >= i64::MIN as f64 && nanos < i64::MAX as f64 {
+            Ok(Self(nanos as i64))
+        } else {
+            Err(DateError::OutOfRange)
+        }
+    }
+}
+
+/// Parses `h:mm`, `h:mm:ss` or `h:mm:ss.fffffffff`, with an optional sign.
+/// Hours are not limited to a day.
+impl FromStr for Time {
+    type Err = DateError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (negative, rest) = match s.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, s.strip_prefix('+').unwrap_or(s)),
+        };
+        let mut parts = rest.split(':');
+        let hours = digits(parts.next().unwrap_or_default(), 12)?;
+        let minutes = digits(parts.next().ok_or(DateError::Invalid)?, 2)?;
+        let (seconds, fraction) = match parts.next() {
+            Some(seconds) => match seconds.split_once('.') {
+                Some((seconds, fraction)) => (seconds, Some(fraction)),
+                None => (seconds, None),
+            },
+            None => ("0", None),
+        };
+        if parts.next().is_some() {
+            return Err(DateError::Invalid);
+        }
+        let seconds = digits(seconds, 2)?;
+        if minutes >= 60 || seconds >= 60 {
+            return Err(DateError::OutOfRange);
+        }
+        let nanos = match fraction {
+            Some(fraction) => digits(fraction, 9)? * 10i64.pow(9 - fraction.len() as u32),
+            None => 0,
+        };
+        let time = hours
+            .checked_mul(Self::HOUR)
+            .and_then(|time| time.checked_add(minutes * Self::MINUTE + seconds * Self::SECOND))
+            .and_then(|time| time.checked_add(nanos))
+            .ok_or(DateError::OutOfRange)?;
+        Ok(Self(if negative { -time } else { time }))
+    }
+}
+
+/// Formats as `10:30`, or `10:30:15.5` when there are seconds.
+impl fmt::Display for Time {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let sign = if self.0 < 0 { "-" } else { "" };
+        let nanos = self.0.unsigned_abs();
+        let (hours, minutes) = (nanos / Self::HOUR as u64, nanos / Self::MINUTE as u64 % 60);
+        write!(f, "{sign}{hours}:{minutes:02}")?;
+        let seconds = nanos % Self::MINUTE as u64;
+        if seconds != 0 {
+            write!(f, ":{:02}", seconds / Self::SECOND as u64)?;
+            let fraction = format!("{:09}", seconds % Self::SECOND as u64);
+            let fraction = fraction.trim_end_matches('0');
+            if !fraction.is_empty() {
+                write!(f, ".{fraction}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A calendar day with an optional time of day and time zone.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Date {
+    /// Days since 1-Jan-1970
+    days: i32,
+    /// Milliseconds since midnight
+    millis: Option<u32>,
+    /// Offset from UTC in minutes
+    zone: Option<i16>,
+}
+
+impl Date {
+    pub const MIN_YEAR: i64 = 0;
+    pub const MAX_YEAR: i64 = 9999;
+    /// Zone offsets are kept in quarter hours
+    const ZONE_STEP: i16 = 15;
+
+    pub fn new(year: i64, month: u32, day: u32) -> Result<Self, DateError> {
+        if !(Self::MIN_YEAR..=Self::MAX_YEAR).contains(&year)
+            || !(1..=12).contains(&month)
+            || day < 1
+            || day > days_in_month(year, month)
+        {
+            return Err(DateError::OutOfRange);
+        }
+        Ok(Self {
+            days: days_from_civil(year, month, day) as i32,
+            millis: None,
+            zone: None,
+        })
+    }
+
+    /// Sets the time of day, which must lie within the day. Times within
+    /// dates are kept to the millisecond, so a finer time is out of range
+    /// rather than truncated.
+    pub fn with_time(self, time: Option<Time>) -> Result<Self, DateError> {
+        let millis = match time {
+            Some(time)
+                if !(0..Time::DAY).contains(&time.nanos()) || time.nanos() % 1_000_000 != 0 =>
+            {
+                return Err(DateError::OutOfRange);
+            }
+            Some(time) => Some((time.nanos() / 1_000_000) as u32),
+            None => None,
+        };
+        Ok(Self { millis, ..self })
+    }
+
+    /// Sets the offset from UTC in minutes, a multiple of 15 under 16 hours.
+    pub fn with_zone(self, zone: Option<i16>) -> Result<Self, DateError> {
+        if let Some(zone) = zone
+            && (zone % Self::ZONE_STEP != 0 || zone.abs() >= 16 * 60)
+        {
+            return Err(DateError::OutOfRange);
+        }
+        Ok(Self { zone, ..self })
+    }
+
+    pub fn year(&self) -> i64 {
+        civil_from_days(self.days as i64).0
+    }
+
+    pub fn month(&self) -> u32 {
+        civil_from_days(self.days as i64).1
+    }
+
+    pub fn day(&self) -> u32 {
+        civil_from_days(self.days as i64).2
+    }
+
+    pub fn time(&self) -> Option<Time> {
+        self.millis
+            .map(|millis| Time::from_nanos(millis as i64 * 1_000_000))
+    }
+
+    pub fn zone(&self) -> Option<i16> {
+        self.zone
+    }
+
+    /// Day of the week, 1 for Monday to 7 for Sunday
+    pub fn weekday(&self) -> u32 {
+        // 1-Jan-1970 was a Thursday
+        ((self.days as i64 + 3).rem_euclid(7) + 1) as u32
+    }
+
+    /// Day of the year, 1 for the 1st of January
+    pub fn yearday(&self) -> u32 {
+        let first = days_from_civil(self.year(), 1, 1);
+        (self.days as i64 - first + 1) as u32
+    }
+
+    /// Returns the same date without its time and zone
+    pub fn date(&self) -> Self {
+        Self {
+            millis: None,
+            zone: None,
+            ..*self
+        }
+    }
+
+    fn from_days(days: i64, millis: Option<u32>, zone: Option<i16>) -> Result<Self, DateError> {
+        let year = civil_from_days(days).0;
+        if !(Self::MIN_YEAR..=Self::MAX_YEAR).contains(&year) {
+            return Err(DateError::OutOfRange);
+        }
+        Ok(Self {
+            days: days as i32,
+            millis,
+            zone,
+        })
+    }
+
+    pub fn add_days(self, days: i64) -> Result<Self, DateError> {
+        let days = (self.days as i64)
+            .checked_add(days)
+            .ok_or(DateError::OutOfRange)?;
+        Self::from_days(days, self.millis, self.zone)
+    }
+
+    /// Moves the date by a duration, carrying over into days. A date
+    /// without a time is taken at midnight, and the duration is rounded
+    /// down to the millisecond.
+    pub fn add_time(self, time: Time) -> Result<Self, DateError> {
+        let millis = self.millis.unwrap_or(0) as i64 + time.nanos().div_euclid(1_000_000);
+        let day = Time::DAY / 1_000_000;
+        let days = self.days as i64 + millis.div_euclid(day);
+        Self::from_days(days, Some(millis.rem_euclid(day) as u32), self.zone)
+    }
+
+    /// Milliseconds since 1-Jan-1970 UTC. Dates without a time are taken at
+    /// midnight, dates without a zone in UTC.
+    pub fn utc_millis(&self) -> i64 {
+        let local = self.days as i64 * (Time::DAY / 1_000_000) + self.millis.unwrap_or(0) as i64;
+        local - self.zone.unwrap_or(0) as i64 * 60_000
+    }
+
+    /// Returns the time elapsed from `other` to this date, which overflows
+    /// for dates about 292 years apart.
+    pub fn since(&self, other: &Date) -> Result<Time, DateError> {
+        (self.utc_millis() - other.utc_millis())
+            .checked_mul(1_000_000)
+            .map(Time::from_nanos)
+            .ok_or(DateError::OutOfRange)
+    }
+
+    /// Returns the calendar days from `other` to this date, regardless of
+    /// times and zones
+    pub fn days_since(&self, other: &Date) -> i64 {
+        self.days as i64 - other.days as i64
+    }
+
+    pub fn to_bits(&self) -> u64 {
+        let zone = match self.zone {
+            Some(zone) => 1 << 7 | (zone / Self::ZONE_STEP) as u64 & 0x7f,
+            None => 0,
+        };
+        let time = match self.millis {
+            Some(millis) => 1 << 8 | (millis as u64) << 9,
+            None => 0,
+        };
+        (self.days as i64 as u64) << 36 | time | zone
+    }
+
+    pub fn from_bits(bits: u64) -> Self {
+        let zone = (bits & 1 << 7 != 0).then(|| {
+            // sign-extend the 7-bit quarter hours
+            ((bits as u8) << 1) as i8 as i16 / 2 * Self::ZONE_STEP
+        });
+        let millis = (bits & 1 << 8 != 0).then_some((bits >> 9) as u32 & 0x7ff_ffff);
+        Self {
+            days: (bits as i64 >> 36) as i32,
+            millis,
+            zone,
+        }
+    }
+}
+
+/// Parses `2026-10-16`, `16-Oct-2026` or `16-10-2026`, optionally followed
+/// by `/` and a time of day, itself optionally followed by a zone offset
+/// like `+2:00`, `-0530` or `Z`.
+impl FromStr for Date {
+    type Err = DateError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (date, time) = match s.split_once('/') {
+            Some((date, time)) => (date, Some(time)),
+            None => (s, None),
+        };
+        let parts: Vec<&str> = date.split('-').collect();
+        let &[first, second, third] = parts.as_slice() else {
+            return Err(DateError::Invalid);
+        };
+        let (year, month, day) = if first.len() == 4 {
+            (digits(first, 4)?, digits(second, 2)?, digits(third, 2)?)
+        } else {
+            let month = match digits(second, 2) {
+                Ok(month) => month,
+                Err(_) => month_number(second)?,
+            };
+            (digits(third, 4)?, month, digits(first, 2)?)
+        };
+        let date = Date::new(year, month as u32, day as u32)?;
+        let Some(time) = time else {
+            return Ok(date);
+        };
+
+        let (time, zone) = if let Some(time) = time.strip_suffix('Z') {
+            (time, Some(0))
+        } else if let Some(at) = time.rfind(['+', '-']) {
+            (&time[..at], Some(parse_zone(&time[at..])?))
+        } else {
+            (time, None)
+        };
+        if time.starts_with(['+', '-']) {
+            return Err(DateError::Invalid);
+        }
+        date.with_time(Some(time.parse()?))?.with_zone(zone)
+    }
+}
+
+/// Parses a month name or its first three letters, in any case
+fn month_number(name: &str) -> Result<i64, DateError> {
+    MONTHS
+        .iter()
+        .position(|month| {
+            name.len() >= 3
+                && month.len() >= name.len()
+                && month[..name.len()].eq_ignore_ascii_case(name)
+        })
+        .map(|index| index as i64 + 1)
+        .ok_or(DateError::Invalid)
+}
+
+/// Parses `+2`, `+2:00` or `-0530` into minutes
+fn parse_zone(zone: &str) -> Result<i16, DateError> {
+    let negative = zone.starts_with('-');
+    let zone = &zone[1..];
+    let (hours, minutes) = match zone.split_once(':') {
+        Some((hours, minutes)) => (digits(hours, 2)?, digits(minutes, 2)?),
+        None if zone.len() == 4 => (digits(&zone[..2], 2)?, digits(&zone[2..], 2)?),
+        None => (digits(zone, 2)?, 0),
+    };
+    if minutes >= 60 {
+        return Err(DateError::OutOfRange);
+    }
+    let minutes = (hours * 60 + minutes) as i16;
+    Ok(if negative { -minutes } else { minutes })
+}
+
+/// Formats as `16-Oct-2026` or `16-Oct-2026/10:30:15+2:00`.
+impl fmt::Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (year, month, day) = civil_from_days(self.days as i64);
+        let month = &MONTHS[month as usize - 1][..3];
+        write!(f, "{day}-{month}-{year}")?;
+        if let Some(time) = self.time() {
+            write!(f, "/{time}")?;
+            if let Some(zone) = self.zone {
+                let sign = if zone < 0 { '-' } else { '+' };
+                let zone = zone.unsigned_abs();
+                write!(f, "{sign}{}:{:02}", zone / 60, zone % 60)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn is_leap_year(year: i64) -> bool {
+    year % 4 == 0 && (year % 100 != 0 || year % 400 == 0)
+}
+
+fn days_in_month(year: i64, month: u32) -> u32 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Days since 1-Jan-1970 of a Gregorian date, after Howard Hinnant's
+/// `days_from_civil`.
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year.rem_euclid(400);
+    let month = month as i64;
+    let day_of_year =
+        (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + day as i64 - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146097 + day_of_era - 719468
+}
+
+/// Gregorian date of a day since 1-Jan-1970, the inverse of
+/// `days_from_civil`.
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let days = days + 719468;
+    let era = days.div_euclid(146097);
+    let day_of_era = days.rem_euclid(146097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * shifted_month + 2) / 5 + 1) as u32;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    } as u32;
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(s: &str) -> Date {
+        s.parse().unwrap()
+    }
+
+    fn time(s: &str) -> Time {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_time_parse_and_display() {
+        assert_eq!(time("10:30").nanos(), 10 * Time::HOUR + 30 * Time::MINUTE);
+        assert_eq!(time("10:30:15.5").second(), 15.5);
+        assert_eq!(time("-1:30").hour(), -1);
+        assert_eq!(time("-1:30").minute(), 30);
+        assert_eq!(time("100:00").hour(), 100);
+        assert_eq!(time("0:00:00.000000001").nanos(), 1);
+
+        for invalid in [
+            "10",
+            "10:",
+            "10:3a",
+            "10:30:15:1",
+            ":30",
+            "1:2:3.",
+            "1:2:3.1234567890",
+        ] {
+            assert_eq!(
+                invalid.parse::<Time>(),
+                Err(DateError::Invalid),
+                "{invalid}"
+            );
+        }
+        assert_eq!("10:60".parse::<Time>(), Err(DateError::OutOfRange));
+
+        assert_eq!(time("10:30:00").to_string(), "10:30");
+        assert_eq!(time("10:30:15.5").to_string(), "10:30:15.5");
+        assert_eq!(time("-0:00:01").to_string(), "-0:00:01");
+        assert_eq!(time("100:05").to_string(), "100:05");
+    }
+
+    #[test]
+    fn test_date_parse_and_display() {
+        let d = date("2026-10-16");
+        assert_eq!((d.year(), d.month(), d.day()), (2026, 10, 16));
+        assert_eq!(d, date("16-Oct-2026"));
+        assert_eq!(d, date("16-october-2026"));
+        assert_eq!(d, date("16-10-2026"));
+        assert_eq!(d.weekday(), 5);
+        assert_eq!(d.yearday(), 289);
+        assert_eq!(date("1-Jan-1970").weekday(), 4);
+        assert_eq!(date("29-Feb-2000").yearday(), 60);
+
+        let d = date("16-Oct-2026/10:30:00+2:00");
+        assert_eq!(d.time(), Some(time("10:30")));
+        assert_eq!(d.zone(), Some(120));
+        assert_eq!(d.to_string(), "16-Oct-2026/10:30+2:00");
+        assert_eq!(
+            date("2026-10-16/10:30:15.25-0530").to_string(),
+            "16-Oct-2026/10:30:15.25-5:30"
+        );
+        assert_eq!(date("2026-10-16/23:59Z").zone(), Some(0));
+        assert_eq!(date("2026-10-16").to_string(), "16-Oct-2026");
+
+        for invalid in [
+            "2026-10",
+            "2026/10/16",
+            "16-Foo-2026",
+            "16-Oc-2026",
+            "2026-10-16/",
+            "2026-10-16/+1:00",
+        ] {
+            assert_eq!(
+                invalid.parse::<Date>(),
+                Err(DateError::Invalid),
+                "{invalid}"
+            );
+        }
+        for invalid in [
+            "29-Feb-2026",
+            "2026-13-01",
+            "2026-10-16/24:00",
+            "2026-10-16/1:00+1:10",
+            "2026-10-16/10:30:15.5000001",
+        ] {
+            assert_eq!(
+                invalid.parse::<Date>(),
+                Err(DateError::OutOfRange),
+                "{invalid}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_date_arithmetic_and_bits() -> Result<(), DateError> {
+        let d = date("28-Feb-2024/23:00+1:00");
+        assert_eq!(d.add_days(1)?, date("29-Feb-2024/23:00+1:00"));
+        assert_eq!(d.add_time(time("2:00"))?, date("29-Feb-2024/1:00+1:00"));
+        assert_eq!(
+            d.add_time(time("-23:00:00.001"))?,
+            date("27-Feb-2024/23:59:59.999+1:00")
+        );
+        assert_eq!(
+            date("1-Mar-2024").since(&date("1-Feb-2024"))?,
+            time("696:00")
+        );
+        assert_eq!(date("1-Jan-0000").days_since(&date("1-Jan-2000")), -730485);
+        assert_eq!(
+            date("1-Jan-0000").since(&date("1-Jan-2000")),
+            Err(DateError::OutOfRange)
+        );
+        // the same instant in two zones
+        let utc = date("28-Feb-2024/22:00Z");
+        assert_eq!(d.utc_millis(), utc.utc_millis());
+        assert_eq!(date("31-Dec-9999").add_days(1), Err(DateError::OutOfRange));
+        let noon = date("16-Oct-2026").with_time(Some(time("12:00:00.125")))?;
+        assert_eq!(noon.time(), Some(time("12:00:00.125")));
+        assert_eq!(
+            noon.with_time(Some(time("12:00:00.0000001"))),
+            Err(DateError::OutOfRange)
+        );
+
+        for d in [
+            d,
+            utc,
+            date("1-Jan-0000"),
+            date("31-Dec-9999/23:59:59.999-15:45"),
+        ] {
+            assert_eq!(Date::from_bits(d.to_bits()), d);
+        }
+        for days in [-719528, -1, 0, 1, 2932896] {
+            let (year, month, day) = civil_from_days(days);
+            assert_eq!(days_from_civil(year, month, day), days);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,371 @@
+// Rebel™ © 2025 Huly Labs • https://hulylabs.com • SPDX-License-Identifier: MIT
+
+//! Mark-sweep garbage collector for the Memory heap
+//!
+//! Compiled code refers to word slots by absolute address, so the collector
+//! never moves allocations. Dead allocations are merged into a free list
+//! that `Memory` reuses for later allocations.
+//!
+//! Tracing starts from the symbol table and the system words, plus any
+//! [`Roots`] held outside the heap (e.g. the stacks of a running `Process`).
+//! The type of every allocation is known from the reference leading to it:
+//! - blocks, parens and paths of every kind point to series of values,
+//!   whose `bindings` field holds the compiled code cache
+//! - strings, string-likes (`FILE`, `URL`, `EMAIL`, `ISSUE`), `BINARY`
+//!   and words of every kind point to byte series
+//! - `FUNC` values point to a `Func`, which leads to its context and body
+//! - contexts are series of `KeyValue`, whose `bindings` field links the
+//!   parent context
+//! - compiled code holds constants, path keys and addresses of word slots
+//! - the system words table leads to the cells holding their values
+//!
+//! A word slot or native function referenced by address only keeps its
+//! allocation alive, without tracing the rest of it. Natives are also kept
+//! alive by the natives table, since compiled code calls them by id.
+
+use crate::mem::{
+    Address, Block, Func, KeyValue, Memory, MemoryError, NativeFunc, Offset, Series, SystemWord,
+    Type, Value,
+};
+use crate::vm::Code;
+
+/// Heap references held outside of the heap.
+#[derive(Debug, Default)]
+pub struct Roots {
+    values: Vec<Value>,
+    code: Vec<Address>,
+    contexts: Vec<Address>,
+}
+
+impl Roots {
+    /// Adds a value, e.g. from a process stack
+    pub fn value(&mut self, value: Value) {
+        self.values.push(value);
+    }
+
+    /// Adds an address anywhere inside a compiled code series
+    pub fn code(&mut self, address: Address) {
+        self.code.push(address);
+    }
+
+    /// Adds a context, 0 (system words) is ignored
+    pub fn context(&mut self, address: Address) {
+        self.contexts.push(address);
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Mark {
+    Dead,
+    Pinned,
+    Traced,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Trace {
+    Bytes,
+    Symbols,
+    SystemWords,
+    Values,
+    KeyValues,
+    Code,
+    Func,
+    Natives,
+}
+
+/// Returns the constants and the word slot addresses found in a compiled
+/// code series.
+pub(crate) fn scan_code(
+    memory: &Memory,
+    code: Series<u8>,
+) -> Result<(Vec<Value>, Vec<Address>), MemoryError> {
+    let start = code.address() + Block::SIZE;
+    let end = start + memory.len(code)?;
+    let mut ip = start as usize;
+    let read_u32 = |ip: usize| memory.get_u32_le(ip).ok_or(MemoryError::OutOfBounds);
+    let mut values = Vec::new();
+    let mut slots = Vec::new();
+    while ip < end as usize {
+        let op = memory.get_u8(ip).ok_or(MemoryError::OutOfBounds)?;
+        ip += match op {
+            Code::CONST => {
+                let kind = memory.get_u8(ip + 1).ok_or(MemoryError::OutOfBounds)?;
+                values.push(Value::new(kind as Type, read_u32(ip + 2)?));
+                6
+            }
+            Code::CONST_VALUE | Code::SELECT => {
+                let (kind, low) = (read_u32(ip + 1)?, read_u32(ip + 5)?);
+                values.push(Value(kind, low, read_u32(ip + 9)?));
+                13
+            }
+            Code::WORD | Code::SET_WORD | Code::GET_WORD | Code::CALL_FUNC => {
+                slots.push(read_u32(ip + 1)?);
+                5
+            }
+            Code::LEAVE => 2,
+            Code::CALL_NATIVE => 3,
+            Code::RET | Code::NONE | Code::SELECT_KEY | Code::SET_PATH => 1,
+            _ => return Err(MemoryError::TypeMismatch),
+        };
+    }
+    Ok((values, slots))
+}
+
+struct Marker<'a> {
+    memory: &'a Memory,
+    allocations: Vec<(Address, Offset)>,
+    marks: Vec<Mark>,
+    work: Vec<(Trace, Address)>,
+}
+
+impl<'a> Marker<'a> {
+    fn new(memory: &'a Memory) -> Result<Self, MemoryError> {
+        let allocations = memory.allocations()?;
+        let marks = vec![Mark::Dead; allocations.len()];
+        Ok(Self {
+            memory,
+            allocations,
+            marks,
+            work: Vec::new(),
+        })
+    }
+
+    /// Index of the allocation containing `address`
+    fn find(&self, address: Address) -> Option<usize> {
+        let index = self
+            .allocations
+            .partition_point(|(start, _)| *start <= address)
+            .checked_sub(1)?;
+        let (start, size) = self.allocations[index];
+        (address < start + size).then_some(index)
+    }
+
+    fn trace(&mut self, address: Address, trace: Trace) {
+        if let Some(index) = self.find(address)
+            && self.marks[index] != Mark::Traced
+        {
+            self.marks[index] = Mark::Traced;
+            self.work.push((trace, self.allocations[index].0));
+        }
+    }
+
+    fn pin(&mut self, address: Address) {
+        if let Some(index) = self.find(address)
+            && self.marks[index] == Mark::Dead
+        {
+            self.marks[index] = Mark::Pinned;
+        }
+    }
+
+    fn slot(&mut self, address: Address) -> Result<(), MemoryError> {
+        self.pin(address);
+        let value = self.memory.get::<Value>(address).copied()?;
+        self.value(value)
+    }
+
+    fn value(&mut self, value: Value) -> Result<(), MemoryError> {
+        match value.kind() {
+            Value::BLOCK
+            | Value::PAREN
+            | Value::PATH
+            | Value::LIT_PATH
+            | Value::GET_PATH
+            | Value::SET_PATH => self.trace(value.data(), Trace::Values),
+            Value::STRING
+            | Value::FILE
+            | Value::URL
+            | Value::EMAIL
+            | Value::ISSUE
+            | Value::BINARY
+            | Value::WORD
+            | Value::SET_WORD
+            | Value::GET_WORD
+            | Value::LIT_WORD => self.trace(value.data(), Trace::Bytes),
+            Value::FUNC => self.trace(value.data(), Trace::Func),
+            Value::NATIVE_FUNC => self.native(value.data())?,
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn native(&mut self, address: Address) -> Result<(), MemoryError> {
+        self.pin(address);
+        let native = self.memory.get::<NativeFunc>(address).copied()?;
+        self.trace(native.name().address(), Trace::Bytes);
+        self.trace(native.desc(), Trace::Bytes);
+        Ok(())
+    }
+
+    fn code(&mut self, code: Series<u8>) -> Result<(), MemoryError> {
+        let (values, slots) = scan_code(self.memory, code)?;
+        for value in values {
+            self.value(value)?;
+        }
+        for slot in slots {
+            self.slot(slot)?;
+        }
+        Ok(())
+    }
+
+    fn run(&mut self) -> Result<(), MemoryError> {
+        while let Some((trace, address)) = self.work.pop() {
+            // a grown series keeps its items in the block it forwards to
+            if !matches!(trace, Trace::Func) {
+                let target = self.memory.resolve(address)?;
+                if target != address {
+                    self.pin(target);
+                }
+            }
+            match trace {
+                Trace::Bytes => {}
+                Trace::Symbols => {
+                    let symbols = self.memory.get_table(Series::<Address>::new(address))?;
+                    for &symbol in symbols.iter().filter(|symbol| **symbol != 0) {
+                        self.trace(symbol, Trace::Bytes);
+                    }
+                }
+                Trace::SystemWords => {
+                    let series = Series::<SystemWord>::new(address);
+                    for word in self.memory.get_table(series)? {
+                        if word.key() == 0 {
+                            continue;
+                        }
+                        self.trace(word.key(), Trace::Bytes);
+                        self.slot(word.cell())?;
+                    }
+                }
+                Trace::Values => {
+                    let series = Series::<Value>::new(address);
+                    for &value in self.memory.get_items(series)? {
+                        self.value(value)?;
+                    }
+                    let code = self.memory.get::<Block>(address)?.bindings;
+                    if code != 0 {
+                        self.trace(code, Trace::Code);
+                    }
+                }
+                Trace::KeyValues => {
+                    let series = Series::<KeyValue>::new(address);
+                    for kv in self.memory.get_items(series)? {
+                        self.trace(kv.key(), Trace::Bytes);
+                        self.value(kv.value())?;
+                    }
+                    let parent = self.memory.context_parent(series)?;
+                    if parent != 0 {
+                        self.trace(parent, Trace::KeyValues);
+                    }
+                }
+                Trace::Code => self.code(Series::new(address))?,
+                Trace::Natives => {
+                    let natives = self.memory.get_items(Series::<Address>::new(address))?;
+                    for &native in natives {
+                        self.native(native)?;
+                    }
+                }
+                Trace::Func => {
+                    let func = self.memory.get::<Func>(address + 4).copied()?;
+                    self.trace(func.context().address(), Trace::KeyValues);
+                    self.trace(func.body().address(), Trace::Values);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Memory {
+    /// Frees every allocation not reachable from the symbol table, the
+    /// system words or `roots`, and returns the number of bytes released.
+    ///
+    /// The symbol table itself is never collected, so symbols stay interned.
+    pub fn collect_garbage(&mut self, roots: &Roots) -> Result<Offset, MemoryError> {
+        let (allocations, marks) = {
+            let mut marker = Marker::new(self)?;
+            let header = self.header_roots()?;
+            marker.trace(header.symbol_table.address(), Trace::Symbols);
+            marker.trace(header.system_words.address(), Trace::SystemWords);
+            marker.trace(header.natives.address(), Trace::Natives);
+            for &value in &roots.values {
+                marker.value(value)?;
+            }
+            for &address in &roots.code {
+                marker.trace(address, Trace::Code);
+            }
+            for &address in roots.contexts.iter().filter(|address| **address != 0) {
+                marker.trace(address, Trace::KeyValues);
+            }
+            marker.run()?;
+            (marker.allocations, marker.marks)
+        };
+        let live: Vec<bool> = marks.iter().map(|mark| *mark != Mark::Dead).collect();
+        self.sweep(&allocations, &live)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_collect_reuses_space() -> Result<(), MemoryError> {
+        let mut memory = Memory::new(65536)?;
+        let heap = memory.allocations()?.len();
+
+        memory.alloc_string("garbage")?;
+        let kept = memory.alloc_items(&[Value::int(1), Value::int(2)])?;
+        let dead = memory.alloc_items(&[Value::int(3)])?;
+
+        let mut roots = Roots::default();
+        roots.value(Value::block(kept));
+        let released = memory.collect_garbage(&roots)?;
+        assert!(released > 0);
+        // the trailing dead block goes back to the bump allocator
+        assert_eq!(memory.allocations()?.len(), heap + 2);
+
+        let small = memory.alloc_string("abc")?;
+        assert!(small.address() < kept.address());
+        let reused = memory.alloc_items(&[Value::int(4)])?;
+        assert_eq!(reused.address(), dead.address());
+        assert_eq!(memory.get_items(kept)?, &[Value::int(1), Value::int(2)]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_collect_keeps_grown_series() -> Result<(), MemoryError> {
+        let mut memory = Memory::new(65536)?;
+        let series = memory.alloc::<Value>(1)?;
+        for i in 0..20 {
+            let string = memory.alloc_string(&i.to_string())?;
+            memory.push(series, Value::string(string))?;
+        }
+
+        let mut roots = Roots::default();
+        roots.value(Value::block(series));
+        memory.collect_garbage(&roots)?;
+        memory.alloc_items(&[Value::int(0); 64])?;
+
+        let items = memory.get_items(series)?.to_vec();
+        assert_eq!(items.len(), 20);
+        for (i, item) in items.iter().enumerate() {
+            assert_eq!(memory.get_string(item.as_string()?)?, i.to_string());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_collect_traces_system_words() -> Result<(), MemoryError> {
+        let mut memory = Memory::new(65536)?;
+        let inner = memory.alloc_string("inner")?;
+        let outer = memory.alloc_items(&[Value::string(inner)])?;
+        memory.set_word_str("x", Value::block(outer))?;
+
+        memory.alloc_string("garbage")?;
+        memory.collect_garbage(&Roots::default())?;
+
+        let x = memory.get_or_add_symbol("x")?;
+        let block = memory.get_word(x.address())?.as_block()?;
+        let string = memory.get_item(block, 0)?.as_string()?;
+        assert_eq!(memory.get_string(string)?, "inner");
+        Ok(())
+    }
+}
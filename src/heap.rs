@@ -0,0 +1,527 @@
+// Rebel™ © 2025 Huly Labs • https://hulylabs.com • SPDX-License-Identifier: MIT
+
+//! Heap verifier and statistics
+//!
+//! Both are built on a walker that goes over the heap twice: physically,
+//! from the end of the `MemHeader` through the size word every allocation
+//! starts with, and logically, from the series referenced by the
+//! `MemHeader`, following typed references the way the collector does. The
+//! logical walk assigns a [`Kind`] to every allocation it reaches, and
+//! checks every reference and every Block header on its way.
+//!
+//! Allocations only referenced from outside the heap (e.g. the stacks of a
+//! running `Process`) are not reached, they are counted as unreachable.
+
+use crate::gc::scan_code;
+use crate::mem::{
+    Address, Block, Func, KeyValue, MemHeader, Memory, MemoryError, NativeFunc, Offset, Series,
+    SystemWord, Type, Value,
+};
+use std::collections::BTreeMap;
+use thiserror::Error;
+
+/// What an allocation holds, as known from the references leading to it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Kind {
+    /// Byte series of a string or a symbol
+    String,
+    /// Byte series of a binary
+    Binary,
+    /// Series of values of a block or a path
+    Block,
+    /// Series of `KeyValue`
+    Context,
+    /// Compiled code of a block
+    Code,
+    Func,
+    NativeFunc,
+    /// Value of a system word
+    Cell,
+    SymbolTable,
+    SystemWords,
+    Natives,
+}
+
+impl Kind {
+    /// Item size of the series kinds, `None` for single structs
+    fn item_size(self) -> Option<Offset> {
+        let size = match self {
+            Kind::String | Kind::Binary | Kind::Code => 1,
+            Kind::Block => Value::SIZE,
+            Kind::Context => KeyValue::SIZE,
+            Kind::SymbolTable | Kind::Natives => std::mem::size_of::<Address>() as Offset,
+            Kind::SystemWords => std::mem::size_of::<SystemWord>() as Offset,
+            Kind::Func | Kind::NativeFunc | Kind::Cell => return None,
+        };
+        Some(size)
+    }
+}
+
+/// A heap inconsistency found by `Memory::verify`.
+#[derive(Error, Debug)]
+pub enum HeapProblem {
+    #[error("Corrupt memory header: {0}")]
+    Header(MemoryError),
+    #[error("Allocation at {address:#x} has invalid size {size}")]
+    Allocation { address: Address, size: Offset },
+    #[error("Free list entry {address:#x} is not an allocation")]
+    FreeList { address: Address },
+    #[error("Reference at {from:#x} to {to:#x} does not point at a live allocation")]
+    Dangling { from: Address, to: Address },
+    #[error("Allocation at {address:#x} is used as both {first:?} and {second:?}")]
+    Conflict {
+        address: Address,
+        first: Kind,
+        second: Kind,
+    },
+    #[error("Series at {address:#x} has capacity {cap} in an allocation of {size} bytes")]
+    Capacity {
+        address: Address,
+        cap: Offset,
+        size: Offset,
+    },
+    #[error("Series at {address:#x} has length {len} beyond its capacity of {items} items")]
+    Length {
+        address: Address,
+        len: Offset,
+        items: Offset,
+    },
+    #[error("Value at {address:#x} has unknown type {kind}")]
+    Value { address: Address, kind: Type },
+    #[error("Invalid compiled code at {address:#x}: {error}")]
+    Code {
+        address: Address,
+        error: MemoryError,
+    },
+    #[error("Cannot read {kind:?} at {address:#x}: {error}")]
+    Read {
+        address: Address,
+        kind: Kind,
+        error: MemoryError,
+    },
+}
+
+/// Heap usage, as returned by `Memory::stats`.
+#[derive(Debug, Default)]
+pub struct HeapStats {
+    /// Bytes of reachable allocations, headers included, per kind
+    pub bytes: BTreeMap<Kind, Offset>,
+    /// Bytes of the free list
+    pub free: Offset,
+    /// Bytes of allocations neither reachable nor free, garbage or only
+    /// referenced from outside the heap
+    pub unreachable: Offset,
+    /// Address and capacity in bytes of the largest reachable series
+    pub largest_series: Option<(Address, Offset)>,
+    /// Number of interned symbols
+    pub symbols: Offset,
+}
+
+struct Walker<'a> {
+    memory: &'a Memory,
+    allocations: Vec<(Address, Offset)>,
+    free: Vec<bool>,
+    kinds: Vec<Option<Kind>>,
+    work: Vec<(Kind, usize)>,
+    problems: Vec<HeapProblem>,
+}
+
+impl<'a> Walker<'a> {
+    /// Walks the heap physically, and the free list. Stops at the first
+    /// allocation whose size is invalid, since the following ones cannot be
+    /// found.
+    fn new(memory: &'a Memory) -> Result<Self, HeapProblem> {
+        memory.check_header().map_err(HeapProblem::Header)?;
+        let heap_top = memory.heap_top().map_err(HeapProblem::Header)?;
+        let mut address = std::mem::size_of::<MemHeader>() as Address;
+        let mut allocations = Vec::new();
+        while address < heap_top {
+            let size = memory.get::<Offset>(address).copied().unwrap_or(0) & !Block::FORWARDED;
+            if size < 4 || !size.is_multiple_of(4) || size > heap_top - address {
+                return Err(HeapProblem::Allocation { address, size });
+            }
+            allocations.push((address, size));
+            address += size;
+        }
+
+        let mut walker = Self {
+            memory,
+            free: vec![false; allocations.len()],
+            kinds: vec![None; allocations.len()],
+            allocations,
+            work: Vec::new(),
+            problems: Vec::new(),
+        };
+        let chunks = memory.free_chunks().map_err(HeapProblem::Header)?;
+        for (address, size) in chunks {
+            match walker.start(address, 0) {
+                Some(index) if walker.allocations[index].1 == size && !walker.free[index] => {
+                    walker.free[index] = true
+                }
+                _ => walker.problems.push(HeapProblem::FreeList { address }),
+            }
+        }
+        Ok(walker)
+    }
+
+    /// Index of the allocation whose data starts `offset` bytes into it at
+    /// `address`
+    fn start(&self, address: Address, offset: Offset) -> Option<usize> {
+        let chunk = address.checked_sub(offset)?;
+        self.allocations
+            .binary_search_by_key(&chunk, |(start, _)| *start)
+            .ok()
+    }
+
+    /// Index of the allocation containing `address`
+    fn containing(&self, address: Address) -> Option<usize> {
+        let index = self
+            .allocations
+            .partition_point(|(start, _)| *start <= address)
+            .checked_sub(1)?;
+        let (start, size) = self.allocations[index];
+        (address < start + size).then_some(index)
+    }
+
+    /// Records a reference at `from` to an allocation of `kind` at `to`.
+    fn reference(&mut self, from: Address, to: Address, kind: Kind) {
+        // structs are allocated after their size word
+        let offset = match kind {
+            Kind::Func | Kind::NativeFunc | Kind::Cell => 4,
+            _ => 0,
+        };
+        let index = match self.start(to, offset) {
+            Some(index) if !self.free[index] => index,
+            _ => return self.problems.push(HeapProblem::Dangling { from, to }),
+        };
+        match self.kinds[index] {
+            None => {
+                self.kinds[index] = Some(kind);
+                self.work.push((kind, index));
+            }
+            Some(first) if first != kind => self.problems.push(HeapProblem::Conflict {
+                address: to,
+                first,
+                second: kind,
+            }),
+            Some(_) => {}
+        }
+    }
+
+    /// Records a reference at `from` to a word slot, which lies inside a
+    /// context, a cell, or a block holding a `FUNC` value.
+    fn slot(&mut self, from: Address, slot: Address) {
+        match self.containing(slot) {
+            Some(index) if !self.free[index] => match self.memory.get::<Value>(slot) {
+                Ok(value) => self.value(slot, *value),
+                Err(_) => self.problems.push(HeapProblem::Dangling { from, to: slot }),
+            },
+            _ => self.problems.push(HeapProblem::Dangling { from, to: slot }),
+        }
+    }
+
+    fn value(&mut self, address: Address, value: Value) {
+        let data = value.data();
+        match value.kind() {
+            Value::BLOCK
+            | Value::PAREN
+            | Value::PATH
+            | Value::LIT_PATH
+            | Value::GET_PATH
+            | Value::SET_PATH => self.reference(address, data, Kind::Block),
+            Value::STRING
+            | Value::FILE
+            | Value::URL
+            | Value::EMAIL
+            | Value::ISSUE
+            | Value::WORD
+            | Value::SET_WORD
+            | Value::GET_WORD
+            | Value::LIT_WORD => self.reference(address, data, Kind::String),
+            Value::BINARY => self.reference(address, data, Kind::Binary),
+            Value::FUNC => self.reference(address, data, Kind::Func),
+            Value::NATIVE_FUNC => self.reference(address, data, Kind::NativeFunc),
+            Value::NONE
+            | Value::INT
+            | Value::BOOL
+            | Value::FLOAT
+            | Value::MONEY
+            | Value::DATE
+            | Value::TIME
+            | Value::TUPLE
+            | Value::PAIR
+            | Value::CHAR => {}
+            kind => self.problems.push(HeapProblem::Value { address, kind }),
+        }
+    }
+
+    /// Checks the Block header of a series allocation. Returns false if the
+    /// header is broken, or if it forwards to the Block holding the items,
+    /// which is then visited on its own.
+    fn series(&mut self, kind: Kind, index: usize) -> Result<bool, MemoryError> {
+        let (address, size) = self.allocations[index];
+        let item_size = kind.item_size().unwrap_or(1);
+        let block = self.memory.get::<Block>(address).copied()?;
+        if let Some(target) = block.forwarded() {
+            let forwarded_again = self.start(target, 0).is_some()
+                && self.memory.get::<Block>(target)?.forwarded().is_some();
+            if forwarded_again {
+                self.problems.push(HeapProblem::Dangling {
+                    from: address,
+                    to: target,
+                });
+            } else {
+                self.reference(address, target, kind);
+            }
+            return Ok(false);
+        }
+        if block.cap() != size || size < Block::SIZE {
+            self.problems.push(HeapProblem::Capacity {
+                address,
+                cap: block.cap(),
+                size,
+            });
+            return Ok(false);
+        }
+        let items = (size - Block::SIZE) / item_size;
+        if block.len() > items {
+            self.problems.push(HeapProblem::Length {
+                address,
+                len: block.len(),
+                items,
+            });
+            return Ok(false);
+        }
+        Ok(true)
+    }
+
+    fn roots(&mut self) -> Result<(), MemoryError> {
+        let roots = self.memory.header_roots()?;
+        self.reference(0, roots.symbol_table.address(), Kind::SymbolTable);
+        self.reference(0, roots.system_words.address(), Kind::SystemWords);
+        if roots.natives.address() != 0 {
+            self.reference(0, roots.natives.address(), Kind::Natives);
+        }
+        Ok(())
+    }
+
+    fn run(&mut self) {
+        while let Some((kind, index)) = self.work.pop() {
+            let address = self.allocations[index].0;
+            if let Err(error) = self.visit(kind, index) {
+                self.problems.push(HeapProblem::Read {
+                    address,
+                    kind,
+                    error,
+                });
+            }
+        }
+    }
+
+    fn visit(&mut self, kind: Kind, index: usize) -> Result<(), MemoryError> {
+        let memory = self.memory;
+        let address = self.allocations[index].0;
+        let has_items = kind.item_size().is_none() || self.series(kind, index)?;
+        let items = address + Block::SIZE;
+        match kind {
+            Kind::String | Kind::Binary => {}
+            Kind::Block => {
+                if has_items {
+                    let values = memory.get_items(Series::<Value>::new(address))?;
+                    for (i, value) in values.iter().enumerate() {
+                        self.value(items + i as Offset * Value::SIZE, *value);
+                    }
+                }
+                // a grown series keeps its bindings in its original header
+                let code = memory.get::<Block>(address)?.bindings;
+                if code != 0 {
+                    self.reference(address, code, Kind::Code);
+                }
+            }
+            Kind::Context => {
+                if has_items {
+                    let words = memory.get_items(Series::<KeyValue>::new(address))?;
+                    for (i, kv) in words.iter().enumerate() {
+                        let at = items + i as Offset * KeyValue::SIZE;
+                        self.reference(at, kv.key(), Kind::String);
+                        self.value(at + 4, kv.value());
+                    }
+                }
+                let parent = memory.get::<Block>(address)?.bindings;
+                if parent != 0 {
+                    self.reference(address, parent, Kind::Context);
+                }
+            }
+            _ if !has_items => {}
+            Kind::Code => match scan_code(memory, Series::new(address)) {
+                Ok((values, slots)) => {
+                    for value in values {
+                        self.value(address, value);
+                    }
+                    for slot in slots {
+                        self.slot(address, slot);
+                    }
+                }
+                Err(error) => self.problems.push(HeapProblem::Code { address, error }),
+            },
+            Kind::Func => {
+                let func = memory.get::<Func>(address + 4)?;
+                self.reference(address, func.context().address(), Kind::Context);
+                self.reference(address, func.body().address(), Kind::Block);
+            }
+            Kind::NativeFunc => {
+                let native = memory.get::<NativeFunc>(address + 4)?;
+                self.reference(address, native.name().address(), Kind::String);
+                if native.desc() != 0 {
+                    self.reference(address, native.desc(), Kind::String);
+                }
+            }
+            Kind::Cell => {
+                let value = memory.get::<Value>(address + 4)?;
+                self.value(address + 4, *value);
+            }
+            Kind::SymbolTable => {
+                let symbols = memory.get_table(Series::<Address>::new(address))?;
+                for (i, symbol) in symbols.iter().enumerate() {
+                    if *symbol != 0 {
+                        self.reference(items + i as Offset * 4, *symbol, Kind::String);
+                    }
+                }
+            }
+            Kind::SystemWords => {
+                const SIZE: Offset = std::mem::size_of::<SystemWord>() as Offset;
+                let words = memory.get_table(Series::<SystemWord>::new(address))?;
+                for (i, word) in words.iter().enumerate() {
+                    if word.key() != 0 {
+                        let at = items + i as Offset * SIZE;
+                        self.reference(at, word.key(), Kind::String);
+                        self.reference(at + 4, word.cell(), Kind::Cell);
+                    }
+                }
+            }
+            Kind::Natives => {
+                let natives = memory.get_items(Series::<Address>::new(address))?;
+                for (i, native) in natives.iter().enumerate() {
+                    self.reference(items + i as Offset * 4, *native, Kind::NativeFunc);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn walk(memory: &'a Memory) -> Result<Self, HeapProblem> {
+        let mut walker = Self::new(memory)?;
+        if let Err(error) = walker.roots() {
+            walker.problems.push(HeapProblem::Header(error));
+        }
+        walker.run();
+        Ok(walker)
+    }
+}
+
+impl Memory {
+    /// Checks that the heap is consistent: the header, the size of every
+    /// allocation, the free list, and everything reachable from the
+    /// `MemHeader`, i.e. Block headers, values and compiled code. Returns
+    /// every problem found.
+    pub fn verify(&self) -> Result<(), Vec<HeapProblem>> {
+        let walker = Walker::walk(self).map_err(|problem| vec![problem])?;
+        if walker.problems.is_empty() {
+            Ok(())
+        } else {
+            Err(walker.problems)
+        }
+    }
+
+    /// Returns heap usage per kind of allocation. Fails if the heap cannot
+    /// be walked; parts of the heap that are inconsistent in other ways are
+    /// counted as well as possible, use `verify` to find them.
+    pub fn stats(&self) -> Result<HeapStats, MemoryError> {
+        let walker = Walker::walk(self).map_err(|_| MemoryError::OutOfBounds)?;
+        let mut stats = HeapStats::default();
+        for (index, &(address, size)) in walker.allocations.iter().enumerate() {
+            match walker.kinds[index] {
+                Some(kind) => {
+                    *stats.bytes.entry(kind).or_default() += size;
+                    let is_series = kind.item_size().is_some()
+                        && self.get::<Block>(address)?.forwarded().is_none();
+                    let largest = stats.largest_series.map_or(0, |(_, size)| size);
+                    if is_series && size > largest {
+                        stats.largest_series = Some((address, size));
+                    }
+                    if kind == Kind::SymbolTable {
+                        stats.symbols = self.get::<Block>(address)?.len();
+                    }
+                }
+                None if walker.free[index] => stats.free += size,
+                None => stats.unreachable += size,
+            }
+        }
+        Ok(stats)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_verify_consistent_heap() -> Result<(), MemoryError> {
+        let mut memory = Memory::new(65536)?;
+        let series = memory.alloc::<Value>(1)?;
+        for i in 0..10 {
+            let string = memory.alloc_string(&i.to_string())?;
+            memory.push(series, Value::string(string))?;
+        }
+        memory.set_word_str("x", Value::block(series))?;
+        memory.alloc_string("garbage")?;
+        memory.collect_garbage(&Default::default())?;
+
+        assert!(memory.verify().is_ok());
+        let stats = memory.stats()?;
+        assert_eq!(stats.symbols, 1);
+        assert_eq!(stats.bytes.get(&Kind::Cell), Some(&16));
+        assert!(stats.bytes[&Kind::String] >= 10 * 16);
+        assert!(stats.free > 0);
+        let (largest, _) = stats.largest_series.expect("no series");
+        assert_eq!(largest, memory.header_roots()?.system_words.address());
+        Ok(())
+    }
+
+    #[test]
+    fn test_verify_after_exec() -> Result<(), crate::vm::VmError> {
+        let mut vm = crate::vm::Vm::new(Memory::new(65536)?)?;
+        let block =
+            vm.parse_block("sum: func [n] [either lt n 1 [0] [add n sum add n -1]] sum 5")?;
+        let mut process = crate::vm::Process::new(&mut vm)?;
+        let code = process.compile(block.as_block()?)?;
+        process.exec(code)?;
+        process.collect_garbage()?;
+
+        if let Err(problems) = process.memory().verify() {
+            panic!("{problems:?}");
+        }
+        let stats = process.memory().stats()?;
+        assert!(stats.bytes.contains_key(&Kind::Func));
+        assert!(stats.bytes.contains_key(&Kind::Code));
+        assert!(stats.bytes.contains_key(&Kind::NativeFunc));
+        Ok(())
+    }
+
+    #[test]
+    fn test_verify_reports_problems() -> Result<(), MemoryError> {
+        let mut memory = Memory::new(65536)?;
+        let block = memory.alloc_items(&[Value::int(1), Value::block(Series::new(4))])?;
+        memory.set_word_str("x", Value::block(block))?;
+        memory.get_mut::<Value>(block.address() + Block::SIZE)?.0 = 99;
+
+        let problems = memory.verify().expect_err("heap is corrupt");
+        assert_eq!(problems.len(), 2);
+        assert!(matches!(
+            problems[0],
+            HeapProblem::Value { kind: 99, address } if address == block.address() + Block::SIZE
+        ));
+        assert!(matches!(problems[1], HeapProblem::Dangling { to: 4, .. }));
+        Ok(())
+    }
+}
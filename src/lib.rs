@@ -1,6 +1,12 @@
 // Rebel™ © 2025 Huly Labs • https://hulylabs.com • SPDX-License-Identifier: MIT
 
+pub mod compare;
+pub mod date;
+pub mod gc;
+pub mod heap;
 pub mod mem;
+pub mod money;
 pub mod parse;
 mod stdlib;
+pub mod tuple;
 pub mod vm;
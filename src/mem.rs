@@ -1,12 +1,14 @@
 // Rebel™ © 2025 Huly Labs • https://hulylabs.com • SPDX-License-Identifier: MIT
 
+use crate::date::{Date, Time};
+use crate::money::Money;
 use crate::parse::WordKind;
+use crate::tuple::{Pair, Tuple};
 use bytemuck::{
     AnyBitPattern, NoUninit, Pod, PodCastError, Zeroable, try_cast_slice, try_cast_slice_mut,
     try_from_bytes, try_from_bytes_mut,
 };
-use std::collections::hash_map::DefaultHasher;
-use std::hash::{Hash, Hasher};
+use std::collections::{BTreeMap, HashMap};
 use std::ops::Range;
 use thiserror::Error;
 
@@ -26,6 +28,16 @@ pub enum MemoryError {
     OutOfMemory,
     #[error("Word not found")]
     WordNotFound,
+    #[error("Allocation quota exceeded")]
+    QuotaExceeded,
+    #[error("Invalid memory image")]
+    InvalidImage,
+    #[error("Unsupported memory image version {0}")]
+    UnsupportedVersion(Word),
+    #[error("Memory images are not supported on big-endian hosts")]
+    UnsupportedPlatform,
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
     #[error(transparent)]
     TryFromSliceError(#[from] std::array::TryFromSliceError),
 }
@@ -35,6 +47,8 @@ pub type Short = u16;
 pub type Address = Word;
 pub type Offset = Word;
 pub type Type = Offset;
+/// Who heap allocations are accounted to, 0 for nobody
+pub type Owner = Word;
 
 // const SIZE_OF_WORD: usize = std::mem::size_of::<Word>();
 
@@ -60,6 +74,16 @@ pub type Type = Offset;
 ///
 /// The Series<T> type is just a reference to a Block, it holds an address
 /// to the start of the Block and a marker for the contained type.
+///
+/// When a series outgrows its capacity, its items move to a new, larger
+/// Block and the original header becomes a forwarding header: `cap` has the
+/// `FORWARDED` bit set and `len` holds the address of the new Block. The
+/// forwarding header keeps `bindings`, so Series handles and Values pointing
+/// at it stay valid after growth.
+///
+/// For a block, `bindings` caches the code compiled from its items, which
+/// `insert`, `remove`, `change` and `reverse` drop. Contexts use it to link
+/// their parent, and are only ever pushed to.
 #[repr(C)]
 #[derive(Debug, Clone, Copy, Pod, Zeroable)]
 pub struct Block {
@@ -71,6 +95,9 @@ pub struct Block {
 impl Block {
     pub const SIZE: Offset = std::mem::size_of::<Block>() as Offset;
 
+    /// Set in `cap` of a forwarding header, `cap` is otherwise word-aligned
+    pub(crate) const FORWARDED: Offset = 1;
+
     /// Returns the current number of items in the block
     pub fn len(&self) -> Offset {
         self.len
@@ -80,6 +107,16 @@ impl Block {
     pub fn is_empty(&self) -> bool {
         self.len == 0
     }
+
+    /// Returns the capacity in bytes, including the header
+    pub(crate) fn cap(&self) -> Offset {
+        self.cap & !Self::FORWARDED
+    }
+
+    /// Returns the Block a forwarding header points to
+    pub(crate) fn forwarded(&self) -> Option<Address> {
+        (self.cap & Self::FORWARDED != 0).then_some(self.len)
+    }
 }
 
 /// Series represents a reference to a block of homogeneous items in memory.
@@ -130,9 +167,20 @@ impl<T> Series<T> {
 
 //
 
+/// A Rebel value: a type word and two data words.
+///
+/// Most values keep their payload in the low data word and leave the high
+/// one 0; integers and floats use both words for their 64 bits. The low
+/// byte of the type word is the kind of the value (`Value::INT`,
+/// `Value::BLOCK`, ...). Series values (strings and the string-like files,
+/// URLs, emails and issues, binaries, blocks, parens and paths) keep their
+/// position in the series in the rest of the type word, so `next`, `skip`
+/// and friends return a new value over the same series. A position in a
+/// string is the byte offset of a character, and moves by whole characters;
+/// `index?` and lengths still count characters.
 #[repr(C)]
 #[derive(Debug, Clone, Copy, Pod, Zeroable, PartialEq, Eq)]
-pub struct Value(pub Type, pub Word);
+pub struct Value(pub Type, pub Word, pub Word);
 
 impl Value {
     pub const SIZE: Offset = std::mem::size_of::<Value>() as Offset;
@@ -149,56 +197,150 @@ impl Value {
     pub const FLOAT: Type = 9;
     pub const NATIVE_FUNC: Type = 10;
     pub const FUNC: Type = 11;
-
-    pub const VALUE_NONE: Value = Self(Self::NONE, 0);
+    pub const MONEY: Type = 12;
+    pub const BINARY: Type = 13;
+    pub const DATE: Type = 14;
+    pub const TIME: Type = 15;
+    pub const TUPLE: Type = 16;
+    pub const PAIR: Type = 17;
+    pub const FILE: Type = 18;
+    pub const URL: Type = 19;
+    pub const EMAIL: Type = 20;
+    pub const ISSUE: Type = 21;
+    pub const CHAR: Type = 22;
+    pub const PAREN: Type = 23;
+    pub const LIT_WORD: Type = 24;
+    pub const LIT_PATH: Type = 25;
+    pub const GET_PATH: Type = 26;
+    pub const SET_PATH: Type = 27;
+
+    pub const VALUE_NONE: Value = Self(Self::NONE, 0, 0);
+
+    /// Bits of the type word holding the kind, the others hold the index
+    const KIND_BITS: u32 = 8;
+    /// Largest position of a series value
+    pub const MAX_INDEX: Offset = Type::MAX >> Self::KIND_BITS;
 
     pub fn new(kind: Type, data: Word) -> Self {
-        Self(kind, data)
+        Self(kind, data, 0)
+    }
+
+    /// Creates a value with a 64-bit payload
+    pub fn from_bits(kind: Type, bits: u64) -> Self {
+        Self(kind, bits as Word, (bits >> 32) as Word)
     }
 
     pub fn kind(&self) -> Type {
-        self.0
+        self.0 & ((1 << Self::KIND_BITS) - 1)
+    }
+
+    /// Returns the position of a series value in its series, 0 at its head
+    pub fn index(&self) -> Offset {
+        self.0 >> Self::KIND_BITS
+    }
+
+    /// Returns the same series value at position `index`
+    pub fn with_index(&self, index: Offset) -> Result<Self, MemoryError> {
+        if !self.is_series() {
+            Err(MemoryError::TypeMismatch)
+        } else if index > Self::MAX_INDEX {
+            Err(MemoryError::OutOfBounds)
+        } else {
+            Ok(Self(self.kind() | index << Self::KIND_BITS, self.1, self.2))
+        }
     }
 
     pub fn data(&self) -> Word {
         self.1
     }
 
+    /// Returns the 64-bit payload, the high data word above the low one
+    pub fn bits(&self) -> u64 {
+        (self.2 as u64) << 32 | self.1 as u64
+    }
+
     pub fn none() -> Self {
-        Value(Self::NONE, 0)
+        Value::new(Self::NONE, 0)
+    }
+
+    pub fn int(value: i64) -> Self {
+        Value::from_bits(Self::INT, value as u64)
+    }
+
+    pub fn float(value: f64) -> Self {
+        Value::from_bits(Self::FLOAT, value.to_bits())
+    }
+
+    pub fn money(value: Money) -> Self {
+        Value::from_bits(Self::MONEY, value.units() as u64)
+    }
+
+    pub fn date(value: Date) -> Self {
+        Value::from_bits(Self::DATE, value.to_bits())
+    }
+
+    pub fn time(value: Time) -> Self {
+        Value::from_bits(Self::TIME, value.nanos() as u64)
     }
 
-    pub fn int(value: i32) -> Self {
-        Value(Self::INT, value as Word)
+    pub fn tuple(value: Tuple) -> Self {
+        Value::from_bits(Self::TUPLE, value.to_bits())
     }
 
-    pub fn float(value: f32) -> Self {
-        let bits = value.to_bits();
-        Value(Self::FLOAT, bits)
+    pub fn pair(value: Pair) -> Self {
+        Value::from_bits(Self::PAIR, value.to_bits())
+    }
+
+    pub fn char(value: char) -> Self {
+        Value::new(Self::CHAR, value as Word)
     }
 
     pub fn bool(value: bool) -> Self {
-        Value(Self::BOOL, value as Word)
+        Value::new(Self::BOOL, value as Word)
     }
 
     pub fn string(value: Series<u8>) -> Self {
-        Value(Self::STRING, value.address)
+        Value::new(Self::STRING, value.address)
+    }
+
+    pub fn file(value: Series<u8>) -> Self {
+        Value::new(Self::FILE, value.address)
+    }
+
+    pub fn url(value: Series<u8>) -> Self {
+        Value::new(Self::URL, value.address)
+    }
+
+    pub fn email(value: Series<u8>) -> Self {
+        Value::new(Self::EMAIL, value.address)
+    }
+
+    pub fn issue(value: Series<u8>) -> Self {
+        Value::new(Self::ISSUE, value.address)
     }
 
     pub fn block(value: Series<Value>) -> Self {
-        Value(Self::BLOCK, value.address)
+        Value::new(Self::BLOCK, value.address)
+    }
+
+    pub fn binary(value: Series<u8>) -> Self {
+        Value::new(Self::BINARY, value.address)
     }
 
     pub fn path(value: Series<Value>) -> Self {
-        Value(Self::PATH, value.address)
+        Value::new(Self::PATH, value.address)
+    }
+
+    pub fn paren(value: Series<Value>) -> Self {
+        Value::new(Self::PAREN, value.address)
     }
 
     pub fn native(id: Word) -> Self {
-        Value(Self::NATIVE_FUNC, id)
+        Value::new(Self::NATIVE_FUNC, id)
     }
 
     pub fn func(address: Address) -> Self {
-        Value(Self::FUNC, address)
+        Value::new(Self::FUNC, address)
     }
 
     /// Returns true if the value is of the given type
@@ -206,6 +348,44 @@ impl Value {
         self.kind() == kind
     }
 
+    /// Returns true if the value is any string, a binary or any block
+    pub fn is_series(&self) -> bool {
+        self.is_any_string() || self.is_binary() || self.is_any_block()
+    }
+
+    /// Returns true if the value is a block, a paren or any path, which all
+    /// hold their items in a series of values
+    pub fn is_any_block(&self) -> bool {
+        matches!(self.kind(), Self::BLOCK | Self::PAREN) || self.is_any_path()
+    }
+
+    /// Returns true if the value is a path, a lit-path, a get-path or a
+    /// set-path
+    pub fn is_any_path(&self) -> bool {
+        matches!(
+            self.kind(),
+            Self::PATH | Self::LIT_PATH | Self::GET_PATH | Self::SET_PATH
+        )
+    }
+
+    /// Returns true if the value is a word, a set-word, a get-word or a
+    /// lit-word
+    pub fn is_any_word(&self) -> bool {
+        matches!(
+            self.kind(),
+            Self::WORD | Self::SET_WORD | Self::GET_WORD | Self::LIT_WORD
+        )
+    }
+
+    /// Returns true if the value is a string, a file, a URL, an email or an
+    /// issue, which all hold their text in a string series
+    pub fn is_any_string(&self) -> bool {
+        matches!(
+            self.kind(),
+            Self::STRING | Self::FILE | Self::URL | Self::EMAIL | Self::ISSUE
+        )
+    }
+
     /// Returns true if the value is a block
     pub fn is_block(&self) -> bool {
         self.is_type(Self::BLOCK)
@@ -221,11 +401,46 @@ impl Value {
         self.is_type(Self::FLOAT)
     }
 
+    /// Returns true if the value is a money amount
+    pub fn is_money(&self) -> bool {
+        self.is_type(Self::MONEY)
+    }
+
+    /// Returns true if the value is a date
+    pub fn is_date(&self) -> bool {
+        self.is_type(Self::DATE)
+    }
+
+    /// Returns true if the value is a time
+    pub fn is_time(&self) -> bool {
+        self.is_type(Self::TIME)
+    }
+
+    /// Returns true if the value is a tuple
+    pub fn is_tuple(&self) -> bool {
+        self.is_type(Self::TUPLE)
+    }
+
+    /// Returns true if the value is a pair
+    pub fn is_pair(&self) -> bool {
+        self.is_type(Self::PAIR)
+    }
+
+    /// Returns true if the value is a character
+    pub fn is_char(&self) -> bool {
+        self.is_type(Self::CHAR)
+    }
+
     /// Returns true if the value is a string
     pub fn is_string(&self) -> bool {
         self.is_type(Self::STRING)
     }
 
+    /// Returns true if the value is a binary
+    pub fn is_binary(&self) -> bool {
+        self.is_type(Self::BINARY)
+    }
+
     /// Returns true if the value is a word
     pub fn is_word(&self) -> bool {
         self.is_type(Self::WORD)
@@ -236,6 +451,10 @@ impl Value {
         self.is_type(Self::PATH)
     }
 
+    pub fn is_paren(&self) -> bool {
+        self.is_type(Self::PAREN)
+    }
+
     pub fn is_bool(&self) -> bool {
         self.is_type(Self::BOOL)
     }
@@ -249,8 +468,9 @@ impl Value {
             WordKind::Word => Self::WORD,
             WordKind::SetWord => Self::SET_WORD,
             WordKind::GetWord => Self::GET_WORD,
+            WordKind::LitWord => Self::LIT_WORD,
         };
-        Value(typ, symbol.address)
+        Value::new(typ, symbol.address)
     }
 
     pub fn as_block(&self) -> Result<Series<Value>, MemoryError> {
@@ -269,6 +489,23 @@ impl Value {
         }
     }
 
+    /// Returns the text series of any string value
+    pub fn as_any_string(&self) -> Result<Series<u8>, MemoryError> {
+        if self.is_any_string() {
+            Ok(Series::new(self.1))
+        } else {
+            Err(MemoryError::TypeMismatch)
+        }
+    }
+
+    pub fn as_binary(&self) -> Result<Series<u8>, MemoryError> {
+        if self.is_binary() {
+            Ok(Series::new(self.1))
+        } else {
+            Err(MemoryError::TypeMismatch)
+        }
+    }
+
     pub fn as_path(&self) -> Result<Series<Value>, MemoryError> {
         if self.is_path() {
             Ok(Series::new(self.1))
@@ -277,17 +514,81 @@ impl Value {
         }
     }
 
-    pub fn as_int(&self) -> Result<i32, MemoryError> {
+    pub fn as_int(&self) -> Result<i64, MemoryError> {
         if self.is_int() {
-            Ok(self.1 as i32)
+            Ok(self.bits() as i64)
         } else {
             Err(MemoryError::TypeMismatch)
         }
     }
 
-    pub fn as_float(&self) -> Result<f32, MemoryError> {
+    pub fn as_float(&self) -> Result<f64, MemoryError> {
         if self.is_float() {
-            Ok(f32::from_bits(self.1))
+            Ok(f64::from_bits(self.bits()))
+        } else {
+            Err(MemoryError::TypeMismatch)
+        }
+    }
+
+    pub fn as_money(&self) -> Result<Money, MemoryError> {
+        if self.is_money() {
+            Ok(Money::from_units(self.bits() as i64))
+        } else {
+            Err(MemoryError::TypeMismatch)
+        }
+    }
+
+    pub fn as_date(&self) -> Result<Date, MemoryError> {
+        if self.is_date() {
+            Ok(Date::from_bits(self.bits()))
+        } else {
+            Err(MemoryError::TypeMismatch)
+        }
+    }
+
+    pub fn as_time(&self) -> Result<Time, MemoryError> {
+        if self.is_time() {
+            Ok(Time::from_nanos(self.bits() as i64))
+        } else {
+            Err(MemoryError::TypeMismatch)
+        }
+    }
+
+    pub fn as_tuple(&self) -> Result<Tuple, MemoryError> {
+        if self.is_tuple() {
+            Ok(Tuple::from_bits(self.bits()))
+        } else {
+            Err(MemoryError::TypeMismatch)
+        }
+    }
+
+    pub fn as_pair(&self) -> Result<Pair, MemoryError> {
+        if self.is_pair() {
+            Ok(Pair::from_bits(self.bits()))
+        } else {
+            Err(MemoryError::TypeMismatch)
+        }
+    }
+
+    pub fn as_char(&self) -> Result<char, MemoryError> {
+        if self.is_char() {
+            char::from_u32(self.1).ok_or(MemoryError::TypeMismatch)
+        } else {
+            Err(MemoryError::TypeMismatch)
+        }
+    }
+
+    pub fn as_paren(&self) -> Result<Series<Value>, MemoryError> {
+        if self.is_paren() {
+            Ok(Series::new(self.1))
+        } else {
+            Err(MemoryError::TypeMismatch)
+        }
+    }
+
+    pub fn as_func(&self) -> Result<Address, MemoryError> {
+        if self.is_func() {
+            Ok(self.1)
         } else {
             Err(MemoryError::TypeMismatch)
         }
@@ -311,6 +612,60 @@ pub struct KeyValue {
     value: Value,
 }
 
+impl KeyValue {
+    pub const SIZE: Offset = std::mem::size_of::<KeyValue>() as Offset;
+
+    pub fn key(&self) -> Address {
+        self.key
+    }
+
+    pub fn value(&self) -> Value {
+        self.value
+    }
+}
+
+//
+
+/// Entry of the system words table: a symbol and the address of the cell
+/// holding its value.
+///
+/// Compiled code refers to system words by the address of their cell, so
+/// cells are allocated apart from the table, which can then be rehashed
+/// into a larger series without moving them.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Pod, Zeroable)]
+pub struct SystemWord {
+    key: Address,
+    cell: Address,
+}
+
+impl SystemWord {
+    pub fn key(&self) -> Address {
+        self.key
+    }
+
+    pub fn cell(&self) -> Address {
+        self.cell
+    }
+}
+
+/// Entry of a hash table in the heap, whose key is 0 when the entry is empty.
+trait TableEntry: AnyBitPattern + NoUninit {
+    fn key(&self) -> Address;
+}
+
+impl TableEntry for Address {
+    fn key(&self) -> Address {
+        *self
+    }
+}
+
+impl TableEntry for SystemWord {
+    fn key(&self) -> Address {
+        self.key
+    }
+}
+
 //
 
 #[repr(C)]
@@ -319,19 +674,27 @@ pub struct NativeFunc {
     arity: u8,
     consume: u8,
     id: Short,
+    name: Address,
     desc: Address,
 }
 
 impl NativeFunc {
-    pub fn new(id: usize, arity: u8, consume: u8, desc: Series<u8>) -> Self {
+    pub fn new(id: usize, arity: u8, consume: u8, name: Series<u8>, desc: Series<u8>) -> Self {
         Self {
             id: id as Short,
+            name: name.address,
             desc: desc.address,
             arity,
             consume,
         }
     }
 
+    /// Symbol the native was registered under, used to re-attach the Rust
+    /// function when a memory image is loaded.
+    pub fn name(&self) -> Series<u8> {
+        Series::new(self.name)
+    }
+
     pub fn arity(&self) -> u8 {
         self.arity
     }
@@ -343,22 +706,35 @@ impl NativeFunc {
     pub fn func_id(&self) -> Short {
         self.id
     }
+
+    pub fn desc(&self) -> Address {
+        self.desc
+    }
 }
 
 //
 
+/// User-defined function created by `func`.
+///
+/// `context` is the function's own context, whose first `arity` slots are
+/// the parameter words of the spec block, and `body` is the (not yet
+/// compiled) body block. The body is compiled lazily on first call, within
+/// the function context, so it may refer to words defined after the
+/// function itself, including the function's own name.
 #[repr(C)]
 #[derive(Debug, Clone, Copy, Pod, Zeroable)]
 pub struct Func {
     arity: Word,
+    context: Address,
     body: Address,
     desc: Address,
 }
 
 impl Func {
-    pub fn new(arity: Word, body: Series<u8>) -> Self {
+    pub fn new(context: Series<KeyValue>, arity: Word, body: Series<Value>) -> Self {
         Self {
             arity,
+            context: context.address,
             body: body.address,
             desc: 0,
         }
@@ -368,72 +744,371 @@ impl Func {
         self.arity as u8
     }
 
-    pub fn body(&self) -> Address {
-        self.body
+    pub fn context(&self) -> Series<KeyValue> {
+        Series::new(self.context)
+    }
+
+    pub fn body(&self) -> Series<Value> {
+        Series::new(self.body)
     }
 }
 
 //
 
+/// Header at address 0 of every Memory, and of every memory image.
+///
+/// An image is the heap from address 0 up to `heap_top`, in a fixed
+/// little-endian layout: every struct is `#[repr(C)]` made of little-endian
+/// words (`NativeFunc` packs two bytes and a little-endian u16 into its first
+/// word), and bytecode operands are little-endian as well. Symbols are
+/// hashed with `symbol_hash`, so the symbol table does not depend on the
+/// host either. `version` is bumped whenever the layout changes.
 #[repr(C)]
 #[derive(Debug, Clone, Copy, Pod, Zeroable)]
 pub struct MemHeader {
     dead_beef: Word,
+    version: Word,
     heap_top: Address,
     symbol_table: Address,
     system_words: Address,
+    free_list: Address,
+    natives: Address,
 }
 
+impl MemHeader {
+    const MAGIC: Word = 0xDEADBEEF;
+    /// Current image format version
+    pub const VERSION: Word = 4;
+}
+
+/// Hash of a symbol name for the symbol table: 32-bit FNV-1a over its UTF-8
+/// bytes. Part of the image format, it must never change without bumping
+/// `MemHeader::VERSION`.
+pub fn symbol_hash(symbol: &str) -> u32 {
+    symbol.bytes().fold(0x811c9dc5, |hash, byte| {
+        (hash ^ byte as u32).wrapping_mul(0x01000193)
+    })
+}
+
+/// Series referenced from the `MemHeader`.
+pub(crate) struct HeaderRoots {
+    pub symbol_table: Series<Address>,
+    pub system_words: Series<SystemWord>,
+    pub natives: Series<Address>,
+}
+
+/// Header of a free heap chunk, linked into `MemHeader.free_list`.
+///
+/// Every heap allocation, live or free, starts with its size in bytes, so the
+/// heap can be walked from the end of `MemHeader` up to `heap_top`.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Pod, Zeroable)]
+struct FreeChunk {
+    size: Offset,
+    next: Address,
+}
+
+/// Bytes of heap held by each owner, and the quotas they are held to.
+///
+/// Allocations made while the current owner is 0 are not accounted.
+#[derive(Default)]
+struct Accounting {
+    owner: Owner,
+    /// Owner and size of every accounted allocation, by address
+    chunks: BTreeMap<Address, (Owner, Offset)>,
+    usage: HashMap<Owner, Offset>,
+    quotas: HashMap<Owner, Offset>,
+}
+
+impl Accounting {
+    fn check(&self, size: Offset) -> Result<(), MemoryError> {
+        let Some(quota) = self.quotas.get(&self.owner) else {
+            return Ok(());
+        };
+        let usage = self.usage.get(&self.owner).copied().unwrap_or(0);
+        if usage.saturating_add(size) > *quota {
+            Err(MemoryError::QuotaExceeded)
+        } else {
+            Ok(())
+        }
+    }
+
+    fn add(&mut self, address: Address, size: Offset) {
+        if self.owner != 0 {
+            self.chunks.insert(address, (self.owner, size));
+            *self.usage.entry(self.owner).or_default() += size;
+        }
+    }
+
+    /// Accounts for freeing `size` bytes at `address`, either a whole
+    /// allocation or the tail of one (see `grow_series`).
+    fn remove(&mut self, address: Address, size: Offset) {
+        let Some((&start, &mut (owner, ref mut chunk_size))) =
+            self.chunks.range_mut(..=address).next_back()
+        else {
+            return;
+        };
+        if address >= start + *chunk_size {
+            return;
+        }
+        let freed = size.min(start + *chunk_size - address);
+        *chunk_size -= freed;
+        if *chunk_size == 0 {
+            self.chunks.remove(&start);
+        }
+        if let Some(usage) = self.usage.get_mut(&owner) {
+            *usage -= freed;
+        }
+    }
+}
+
+/// The Rebel heap: a byte arena addressed by `Address` offsets.
+///
+/// The arena starts at the size passed to `Memory::new` and grows on demand,
+/// up to `limit` bytes. Growing only appends to the arena, so addresses stay
+/// valid.
+///
+/// Allocations are accounted to the current owner (see `set_owner`), which
+/// may be held to a quota.
 pub struct Memory {
-    memory: Box<[u8]>,
+    memory: Vec<u8>,
+    limit: usize,
+    accounting: Accounting,
 }
 
 fn podcast_error(_err: PodCastError) -> MemoryError {
     MemoryError::AlignmentError
 }
 
+/// Returns the byte offset of the character at `index` in `text`, or its
+/// length past the last character.
+pub(crate) fn char_offset(text: &str, index: Offset) -> usize {
+    text.char_indices()
+        .nth(index as usize)
+        .map_or(text.len(), |(at, _)| at)
+}
+
+/// Returns the byte offset `at` in `text` moved back to the start of its
+/// character, and clamped to the length of `text`: a string may have been
+/// changed since a position in it was taken.
+fn char_boundary(text: &str, at: Offset) -> usize {
+    let mut at = (at as usize).min(text.len());
+    while !text.is_char_boundary(at) {
+        at -= 1;
+    }
+    at
+}
+
 impl Memory {
+    /// Largest arena size, every byte must be addressable by an `Address`.
+    pub const MAX_SIZE: usize = Address::MAX as usize & !3;
+
+    /// Initial capacity of the symbol table and of the system words table,
+    /// both grow past it as needed.
+    const TABLE_CAP: usize = 1024;
+
+    /// Creates a memory with an initial arena of `size` bytes, which grows
+    /// on demand up to `Memory::MAX_SIZE`.
     pub fn new(size: usize) -> Result<Self, MemoryError> {
-        let bytes = vec![0u8; size].into_boxed_slice();
-        let mut memory = Self { memory: bytes };
+        Self::with_limit(size, Self::MAX_SIZE)
+    }
+
+    /// Creates a memory with an initial arena of `size` bytes, which grows
+    /// on demand up to `limit` bytes.
+    pub fn with_limit(size: usize, limit: usize) -> Result<Self, MemoryError> {
+        let limit = limit.min(Self::MAX_SIZE);
+        let size = size.max(std::mem::size_of::<MemHeader>());
+        if size > limit {
+            return Err(MemoryError::OutOfMemory);
+        }
+        let mut memory = Self {
+            memory: vec![0u8; size],
+            limit,
+            accounting: Accounting::default(),
+        };
 
         let header = memory.get_mut::<MemHeader>(0)?;
-        header.dead_beef = 0xDEADBEEF;
+        header.dead_beef = MemHeader::MAGIC;
+        header.version = MemHeader::VERSION;
         header.heap_top = std::mem::size_of::<MemHeader>() as Address;
 
-        let symbol_table = memory.alloc::<Address>(1024)?.address;
+        let symbol_table = memory.alloc::<Address>(Self::TABLE_CAP)?.address;
         let header = memory.get_mut::<MemHeader>(0)?;
         header.symbol_table = symbol_table;
 
-        let system_words = memory.alloc::<KeyValue>(1024)?.address;
+        let system_words = memory.alloc::<SystemWord>(Self::TABLE_CAP)?.address;
         let header = memory.get_mut::<MemHeader>(0)?;
         header.system_words = system_words;
 
         Ok(memory)
     }
 
+    /// Reserves `size` bytes (rounded up to whole words) of heap, reusing a
+    /// free chunk when one is large enough, and returns the chunk address and
+    /// its actual size. The chunk is zeroed and starts with its size, and is
+    /// accounted to the current owner.
+    fn reserve(&mut self, size: usize) -> Result<(Address, Offset), MemoryError> {
+        let size = ((size + 3) & !3) as Offset;
+        self.accounting.check(size)?;
+        let (address, size) = self.reserve_chunk(size)?;
+        self.accounting.add(address, size);
+        Ok((address, size))
+    }
+
+    fn reserve_chunk(&mut self, size: Offset) -> Result<(Address, Offset), MemoryError> {
+        let mut prev = 0;
+        let mut chunk = self.get::<MemHeader>(0)?.free_list;
+        while chunk != 0 {
+            let free = self.get::<FreeChunk>(chunk).copied()?;
+            if free.size >= size {
+                let remainder = free.size - size;
+                let (address, size) = if remainder >= Block::SIZE {
+                    // keep the front of the chunk in the free list
+                    self.get_mut::<FreeChunk>(chunk)?.size = remainder;
+                    (chunk + remainder, size)
+                } else {
+                    if prev == 0 {
+                        self.get_mut::<MemHeader>(0)?.free_list = free.next;
+                    } else {
+                        self.get_mut::<FreeChunk>(prev)?.next = free.next;
+                    }
+                    (chunk, free.size)
+                };
+                let bytes = self
+                    .memory
+                    .get_mut(address as usize..(address + size) as usize)
+                    .ok_or(MemoryError::OutOfBounds)?;
+                bytes.fill(0);
+                *self.get_mut::<Offset>(address)? = size;
+                return Ok((address, size));
+            }
+            prev = chunk;
+            chunk = free.next;
+        }
+
+        let heap_top = self.get::<MemHeader>(0)?.heap_top;
+        let new_heap_top = heap_top.checked_add(size).ok_or(MemoryError::OutOfMemory)?;
+        if new_heap_top as usize > self.memory.len() {
+            self.grow(new_heap_top as usize)?;
+        }
+        self.get_mut::<MemHeader>(0)?.heap_top = new_heap_top;
+        *self.get_mut::<Offset>(heap_top)? = size;
+        Ok((heap_top, size))
+    }
+
+    /// Grows the arena to at least `min_size` bytes, doubling it when the
+    /// limit allows.
+    fn grow(&mut self, min_size: usize) -> Result<(), MemoryError> {
+        if min_size > self.limit {
+            return Err(MemoryError::OutOfMemory);
+        }
+        let size = (self.memory.len() * 2).clamp(min_size, self.limit);
+        self.memory.resize(size, 0);
+        Ok(())
+    }
+
+    /// Current size of the arena in bytes
+    pub fn size(&self) -> usize {
+        self.memory.len()
+    }
+
+    /// Size the arena may grow to, in bytes
+    pub fn limit(&self) -> usize {
+        self.limit
+    }
+
+    /// Sets the owner later allocations are accounted to, 0 for nobody, and
+    /// returns the previous one.
+    pub fn set_owner(&mut self, owner: Owner) -> Owner {
+        std::mem::replace(&mut self.accounting.owner, owner)
+    }
+
+    pub fn owner(&self) -> Owner {
+        self.accounting.owner
+    }
+
+    /// Returns the bytes of heap, headers included, currently held by
+    /// allocations accounted to `owner`.
+    pub fn allocated(&self, owner: Owner) -> Offset {
+        self.accounting.usage.get(&owner).copied().unwrap_or(0)
+    }
+
+    /// Limits the bytes `owner` may hold: allocations beyond `quota` fail
+    /// with `MemoryError::QuotaExceeded`. `None` removes the limit.
+    pub fn set_quota(&mut self, owner: Owner, quota: Option<Offset>) {
+        match quota {
+            Some(quota) => self.accounting.quotas.insert(owner, quota),
+            None => self.accounting.quotas.remove(&owner),
+        };
+    }
+
     fn heap_alloc(
         &mut self,
         size_in_bytes: usize,
         init_len: usize,
     ) -> Result<Address, MemoryError> {
-        let len = self.memory.len() as Offset;
-        let cap = std::mem::size_of::<Block>() + size_in_bytes;
-        let cap = (cap + 3) & !3;
+        let (address, cap) = self.reserve(std::mem::size_of::<Block>() + size_in_bytes)?;
+        let block = self.get_mut::<Block>(address)?;
+        block.cap = cap;
+        block.len = init_len as Offset;
+        block.bindings = 0;
+        Ok(address)
+    }
+
+    /// Returns the address of the Block holding the items of the series at
+    /// `address`, following its forwarding header if the series has grown.
+    pub(crate) fn resolve(&self, address: Address) -> Result<Address, MemoryError> {
+        let block = self.get::<Block>(address)?;
+        if block.cap & Block::FORWARDED != 0 {
+            Ok(block.len)
+        } else {
+            Ok(address)
+        }
+    }
+
+    /// Returns a chunk of heap to the free list.
+    fn release(&mut self, address: Address, size: Offset) -> Result<(), MemoryError> {
+        self.accounting.remove(address, size);
         let header = self.get_mut::<MemHeader>(0)?;
-        let heap_top = header.heap_top;
-        let cap = cap as Offset;
-        let new_heap_top = heap_top + cap;
-        if new_heap_top > len {
-            Err(MemoryError::OutOfMemory)
+        let next = header.free_list;
+        header.free_list = address;
+        *self.get_mut::<FreeChunk>(address)? = FreeChunk { size, next };
+        Ok(())
+    }
+
+    /// Moves the items of the series to a new Block with room for at least
+    /// `items` items, at least doubling the capacity, and forwards the
+    /// series header to it. Returns the address of the new Block.
+    fn grow_series<I>(&mut self, series: Series<I>, items: Offset) -> Result<Address, MemoryError> {
+        let item_size = std::mem::size_of::<I>() as Offset;
+        let old = self.resolve(series.address)?;
+        let block = self.get::<Block>(old).copied()?;
+        let cap_items = (block.cap - Block::SIZE) / item_size;
+        let new_cap_items = items.max(cap_items * 2).max(4);
+
+        let new = self.heap_alloc((new_cap_items * item_size) as usize, block.len as usize)?;
+        let start = (old + Block::SIZE) as usize;
+        let end = start + (block.len * item_size) as usize;
+        self.memory
+            .copy_within(start..end, (new + Block::SIZE) as usize);
+
+        if old == series.address {
+            // keep just the header, and release the old data area if it is
+            // large enough to be reused
+            let tail = block.cap - Block::SIZE;
+            let stub = self.get_mut::<Block>(series.address)?;
+            stub.len = new;
+            if tail >= Block::SIZE {
+                stub.cap = Block::SIZE | Block::FORWARDED;
+                self.release(series.address + Block::SIZE, tail)?;
+            } else {
+                stub.cap = block.cap | Block::FORWARDED;
+            }
         } else {
-            header.heap_top = new_heap_top;
-            let block = self.get_mut::<Block>(heap_top)?;
-            block.cap = cap;
-            block.len = init_len as Offset;
-            block.bindings = 0;
-            Ok(heap_top)
+            self.get_mut::<Block>(series.address)?.len = new;
+            self.release(old, block.cap)?;
         }
+        Ok(new)
     }
 
     /// Allocates a new Series with capacity for at least `cap` items of type I.
@@ -456,7 +1131,7 @@ impl Memory {
         items: &[I],
     ) -> Result<Series<I>, MemoryError> {
         let len = items.len();
-        let size_in_bytes = len * std::mem::size_of::<I>();
+        let size_in_bytes = std::mem::size_of_val(items);
         let address = self.heap_alloc(size_in_bytes, len)?;
 
         let bytes = self.get_byte_slice_mut(address, 0..size_in_bytes as Offset)?;
@@ -474,37 +1149,205 @@ impl Memory {
         &mut self,
         item: I,
     ) -> Result<Address, MemoryError> {
-        let len = self.memory.len() as Offset;
         let cap = std::mem::size_of::<Offset>() + std::mem::size_of::<I>();
-        let cap = cap as Offset;
-        let header = self.get_mut::<MemHeader>(0)?;
-        let heap_top = header.heap_top;
-        let new_heap_top = heap_top + cap;
-        if new_heap_top > len {
-            Err(MemoryError::OutOfMemory)
-        } else {
-            header.heap_top = new_heap_top;
-            let block = self.get_mut::<Offset>(heap_top)?;
-            *block = cap;
-            let item_address = heap_top + 4;
-            let item_slot = self.get_mut::<I>(item_address)?;
-            *item_slot = item;
-            Ok(item_address)
+        let (address, _) = self.reserve(cap)?;
+        let item_address = address + 4;
+        let item_slot = self.get_mut::<I>(item_address)?;
+        *item_slot = item;
+        Ok(item_address)
+    }
+
+    /// Returns `(address, size)` of every heap allocation, live or free, in
+    /// address order.
+    pub(crate) fn allocations(&self) -> Result<Vec<(Address, Offset)>, MemoryError> {
+        let heap_top = self.get::<MemHeader>(0)?.heap_top;
+        let mut address = std::mem::size_of::<MemHeader>() as Address;
+        let mut result = Vec::new();
+        while address < heap_top {
+            let size = self.get::<Offset>(address).copied()? & !Block::FORWARDED;
+            if size < 4 || address + size > heap_top {
+                return Err(MemoryError::OutOfBounds);
+            }
+            result.push((address, size));
+            address += size;
         }
+        Ok(result)
     }
 
-    fn get_byte_slice(&self, address: Address, range: Range<Offset>) -> Result<&[u8], MemoryError> {
-        let start = address + Block::SIZE + range.start;
-        let end = address + Block::SIZE + range.end;
-        self.memory
-            .get(start as usize..end as usize)
-            .ok_or(MemoryError::OutOfBounds)
+    /// Returns the series referenced from the `MemHeader`, the heap roots
+    /// every collection starts from.
+    pub(crate) fn header_roots(&self) -> Result<HeaderRoots, MemoryError> {
+        let header = self.get::<MemHeader>(0)?;
+        Ok(HeaderRoots {
+            symbol_table: Series::new(header.symbol_table),
+            system_words: Series::new(header.system_words),
+            natives: Series::new(header.natives),
+        })
     }
 
-    fn get_byte_slice_mut(
-        &mut self,
-        address: Address,
-        range: Range<Offset>,
+    /// Returns the natives table: addresses of `NativeFunc`s indexed by
+    /// native function id, `None` until a `Vm` registers its natives.
+    pub(crate) fn natives(&self) -> Result<Option<Series<Address>>, MemoryError> {
+        let natives = self.get::<MemHeader>(0)?.natives;
+        Ok((natives != 0).then(|| Series::new(natives)))
+    }
+
+    pub(crate) fn set_natives(&mut self, natives: Series<Address>) -> Result<(), MemoryError> {
+        self.get_mut::<MemHeader>(0)?.natives = natives.address;
+        Ok(())
+    }
+
+    /// Writes the heap, from the `MemHeader` up to the heap top, to a file.
+    ///
+    /// The heap is kept in image layout, so only little-endian hosts can
+    /// save and load images; big-endian hosts get `UnsupportedPlatform`.
+    /// Strings and packed fields sit among the words of the heap, so it
+    /// cannot be converted word by word.
+    pub fn save_image(&self, path: impl AsRef<std::path::Path>) -> Result<(), MemoryError> {
+        if cfg!(target_endian = "big") {
+            return Err(MemoryError::UnsupportedPlatform);
+        }
+        let heap_top = self.get::<MemHeader>(0)?.heap_top as usize;
+        let image = self
+            .memory
+            .get(..heap_top)
+            .ok_or(MemoryError::OutOfBounds)?;
+        std::fs::write(path, image).map_err(Into::into)
+    }
+
+    /// Reads a memory image written by `save_image`. The arena may grow up
+    /// to `Memory::MAX_SIZE`.
+    pub fn load_image(path: impl AsRef<std::path::Path>) -> Result<Self, MemoryError> {
+        let memory = std::fs::read(path)?;
+        Self::from_image(memory, Self::MAX_SIZE)
+    }
+
+    fn from_image(memory: Vec<u8>, limit: usize) -> Result<Self, MemoryError> {
+        if cfg!(target_endian = "big") {
+            return Err(MemoryError::UnsupportedPlatform);
+        }
+        if memory.len() > limit {
+            return Err(MemoryError::OutOfMemory);
+        }
+        let memory = Self {
+            memory,
+            limit,
+            accounting: Accounting::default(),
+        };
+        memory.check_header()?;
+        if memory.get::<MemHeader>(0)?.heap_top as usize != memory.memory.len() {
+            return Err(MemoryError::InvalidImage);
+        }
+        memory
+            .allocations()
+            .map_err(|_| MemoryError::InvalidImage)?;
+        Ok(memory)
+    }
+
+    /// Checks the magic number and the version of the `MemHeader`, and that
+    /// the heap top lies within the arena.
+    pub(crate) fn check_header(&self) -> Result<(), MemoryError> {
+        let header = self
+            .get::<MemHeader>(0)
+            .map_err(|_| MemoryError::InvalidImage)?;
+        if header.dead_beef != MemHeader::MAGIC {
+            return Err(MemoryError::InvalidImage);
+        }
+        // older versions would be migrated here; values grew a second data
+        // word in version 4, so earlier images cannot be read as they are
+        match header.version {
+            MemHeader::VERSION => {}
+            version => return Err(MemoryError::UnsupportedVersion(version)),
+        }
+        if header.heap_top as usize > self.memory.len() || header.heap_top % 4 != 0 {
+            return Err(MemoryError::InvalidImage);
+        }
+        Ok(())
+    }
+
+    /// Returns the end of the heap, where bump allocation continues.
+    pub(crate) fn heap_top(&self) -> Result<Address, MemoryError> {
+        self.get::<MemHeader>(0).map(|header| header.heap_top)
+    }
+
+    /// Returns `(address, size)` of the chunks in the free list, in list
+    /// order. Fails if the list loops.
+    pub(crate) fn free_chunks(&self) -> Result<Vec<(Address, Offset)>, MemoryError> {
+        let mut chunk = self.get::<MemHeader>(0)?.free_list;
+        let mut result = Vec::new();
+        let mut seen = std::collections::HashSet::new();
+        while chunk != 0 {
+            if !seen.insert(chunk) {
+                return Err(MemoryError::OutOfBounds);
+            }
+            let free = self.get::<FreeChunk>(chunk)?;
+            result.push((chunk, free.size));
+            chunk = free.next;
+        }
+        Ok(result)
+    }
+
+    /// Rebuilds the free list from the allocations for which `live` is false,
+    /// merging adjacent dead allocations and returning trailing ones to the
+    /// bump allocator. Returns the number of bytes released.
+    pub(crate) fn sweep(
+        &mut self,
+        allocations: &[(Address, Offset)],
+        live: &[bool],
+    ) -> Result<Offset, MemoryError> {
+        let mut free_list = 0;
+        let mut released = 0;
+        let mut run: Option<(Address, Offset)> = None;
+
+        let mut flush = |memory: &mut Self, run: (Address, Offset)| -> Result<(), MemoryError> {
+            let (address, size) = run;
+            let header = memory.get_mut::<MemHeader>(0)?;
+            if address + size == header.heap_top {
+                header.heap_top = address;
+            } else {
+                *memory.get_mut::<FreeChunk>(address)? = FreeChunk {
+                    size,
+                    next: free_list,
+                };
+                free_list = address;
+            }
+            Ok(())
+        };
+
+        for (&(address, size), &live) in allocations.iter().zip(live.iter()) {
+            if live {
+                if let Some(run) = run.take() {
+                    flush(self, run)?;
+                }
+            } else {
+                released += size;
+                self.accounting.remove(address, size);
+                run = match run {
+                    Some((start, run_size)) => Some((start, run_size + size)),
+                    None => Some((address, size)),
+                };
+            }
+        }
+        if let Some(run) = run {
+            flush(self, run)?;
+        }
+
+        self.get_mut::<MemHeader>(0)?.free_list = free_list;
+        Ok(released)
+    }
+
+    fn get_byte_slice(&self, address: Address, range: Range<Offset>) -> Result<&[u8], MemoryError> {
+        let start = address + Block::SIZE + range.start;
+        let end = address + Block::SIZE + range.end;
+        self.memory
+            .get(start as usize..end as usize)
+            .ok_or(MemoryError::OutOfBounds)
+    }
+
+    fn get_byte_slice_mut(
+        &mut self,
+        address: Address,
+        range: Range<Offset>,
     ) -> Result<&mut [u8], MemoryError> {
         let start = address + Block::SIZE + range.start;
         let end = address + Block::SIZE + range.end;
@@ -520,7 +1363,8 @@ impl Memory {
     ) -> Result<&[I], MemoryError> {
         let item_size = std::mem::size_of::<I>() as Offset;
         let range = range.start * item_size..range.end * item_size;
-        let bytes = self.get_byte_slice(series.address, range)?;
+        let address = self.resolve(series.address)?;
+        let bytes = self.get_byte_slice(address, range)?;
         try_cast_slice(bytes).map_err(podcast_error)
     }
 
@@ -531,7 +1375,8 @@ impl Memory {
     ) -> Result<&mut [I], MemoryError> {
         let item_size = std::mem::size_of::<I>() as Offset;
         let range = range.start * item_size..range.end * item_size;
-        let bytes = self.get_byte_slice_mut(series.address, range)?;
+        let address = self.resolve(series.address)?;
+        let bytes = self.get_byte_slice_mut(address, range)?;
         try_cast_slice_mut(bytes).map_err(podcast_error)
     }
 
@@ -546,23 +1391,36 @@ impl Memory {
     }
 
     pub fn get_items<I: AnyBitPattern>(&self, series: Series<I>) -> Result<&[I], MemoryError> {
-        let block = self.get::<Block>(series.address)?;
-        let len = block.len;
+        let len = self.len(series)?;
         self.get_items_slice(series, 0..len)
     }
 
+    /// Returns all items up to the capacity of the series, regardless of its
+    /// length. Used for hash tables, whose entries are spread over the whole
+    /// block and whose empty slots are zeroed.
+    pub(crate) fn get_table<I: AnyBitPattern>(
+        &self,
+        series: Series<I>,
+    ) -> Result<&[I], MemoryError> {
+        let block = self.get::<Block>(self.resolve(series.address)?)?;
+        let item_size = std::mem::size_of::<I>() as Offset;
+        let cap = (block.cap - Block::SIZE) / item_size;
+        self.get_items_slice(series, 0..cap)
+    }
+
     pub fn get_item<I: AnyBitPattern>(
         &self,
         series: Series<I>,
         index: Offset,
     ) -> Result<&I, MemoryError> {
-        let block = self.get::<Block>(series.address)?;
+        let address = self.resolve(series.address)?;
+        let block = self.get::<Block>(address)?;
         let len = block.len;
         if index >= len {
             Err(MemoryError::OutOfBounds)
         } else {
             let item_size = std::mem::size_of::<I>() as Offset;
-            self.get(series.address + Block::SIZE + index * item_size)
+            self.get(address + Block::SIZE + index * item_size)
         }
     }
 
@@ -591,18 +1449,19 @@ impl Memory {
         self.memory.get(address).copied()
     }
 
-    pub fn get_u16_ne(&self, address: usize) -> Option<Short> {
+    pub fn get_u16_le(&self, address: usize) -> Option<Short> {
         let bytes = self.memory.get(address..address + 2)?;
-        bytes.try_into().ok().map(u16::from_ne_bytes)
+        bytes.try_into().ok().map(u16::from_le_bytes)
     }
 
-    pub fn get_u32_ne(&self, address: usize) -> Option<Word> {
+    pub fn get_u32_le(&self, address: usize) -> Option<Word> {
         let bytes = self.memory.get(address..address + 4)?;
-        bytes.try_into().ok().map(u32::from_ne_bytes)
+        bytes.try_into().ok().map(u32::from_le_bytes)
     }
 
     pub fn len<I>(&self, series: Series<I>) -> Result<Offset, MemoryError> {
-        self.get::<Offset>(series.address + 4).copied()
+        let address = self.resolve(series.address)?;
+        self.get::<Offset>(address + 4).copied()
     }
 
     pub fn push<I: AnyBitPattern + NoUninit>(
@@ -610,20 +1469,20 @@ impl Memory {
         series: Series<I>,
         value: I,
     ) -> Result<Address, MemoryError> {
-        let block = self.get_mut::<Block>(series.address)?;
+        let mut address = self.resolve(series.address)?;
+        let block = self.get::<Block>(address)?;
         let len = block.len;
         let item_size = std::mem::size_of::<I>() as Offset;
         let item_start = len * item_size;
         let item_end = item_start + item_size;
-        if block.cap < item_end {
-            Err(MemoryError::StackOverflow)
-        } else {
-            block.len = len + 1;
-            let address = series.address + Block::SIZE + item_start;
-            let item = self.get_mut::<I>(address)?;
-            *item = value;
-            Ok(address)
+        if block.cap < Block::SIZE + item_end {
+            address = self.grow_series(series, len + 1)?;
         }
+        self.get_mut::<Block>(address)?.len = len + 1;
+        let address = address + Block::SIZE + item_start;
+        let item = self.get_mut::<I>(address)?;
+        *item = value;
+        Ok(address)
     }
 
     pub fn push_all<I: AnyBitPattern + NoUninit>(
@@ -631,26 +1490,15 @@ impl Memory {
         series: Series<I>,
         values: &[I],
     ) -> Result<(), MemoryError> {
-        let block = self.get_mut::<Block>(series.address)?;
-        let cap = block.cap;
-        let len = block.len;
-
-        let item_size = std::mem::size_of::<I>() as Offset;
-        let cap_items = (cap - Block::SIZE) / item_size;
-        let items_len = values.len() as Offset;
-        let new_len = len + items_len;
-
-        if new_len > cap_items {
-            Err(MemoryError::StackOverflow)
-        } else {
-            block.len = new_len;
-            let items = self.get_items_slice_mut(series, len..new_len)?;
-            let iter = items.iter_mut().zip(values.iter());
-            for (dst, src) in iter {
-                *dst = *src
-            }
-            Ok(())
+        let len = self.len(series)?;
+        let new_len = len + values.len() as Offset;
+        self.resize(series, new_len)?;
+        let items = self.get_items_slice_mut(series, len..new_len)?;
+        let iter = items.iter_mut().zip(values.iter());
+        for (dst, src) in iter {
+            *dst = *src
         }
+        Ok(())
     }
 
     // pub fn push_n<const N: usize, I: AnyBitPattern + NoUninit>(
@@ -685,15 +1533,15 @@ impl Memory {
 
     pub fn pop<I: AnyBitPattern>(&mut self, series: Series<I>) -> Result<I, MemoryError> {
         let item_size = std::mem::size_of::<I>() as Offset;
-        let block = self.get_mut::<Block>(series.address)?;
+        let address = self.resolve(series.address)?;
+        let block = self.get_mut::<Block>(address)?;
         let len = block.len;
         let new_len = len.checked_sub(1).ok_or(MemoryError::StackUnderflow)?;
 
         let item_start = new_len * item_size;
 
         block.len = new_len;
-        self.get::<I>(series.address + Block::SIZE + item_start)
-            .copied()
+        self.get::<I>(address + Block::SIZE + item_start).copied()
     }
 
     // pub fn pop_n<const N: usize, I: AnyBitPattern>(
@@ -712,7 +1560,8 @@ impl Memory {
 
     pub fn peek<I: AnyBitPattern>(&self, series: Series<I>) -> Result<Option<&I>, MemoryError> {
         let item_size = std::mem::size_of::<I>() as Offset;
-        let block = self.get::<Block>(series.address)?;
+        let address = self.resolve(series.address)?;
+        let block = self.get::<Block>(address)?;
         let len = block.len;
 
         if len == 0 {
@@ -720,8 +1569,7 @@ impl Memory {
         } else {
             let item_offset = len - 1;
             let item_start = item_offset * item_size;
-            self.get::<I>(series.address + Block::SIZE + item_start)
-                .map(Some)
+            self.get::<I>(address + Block::SIZE + item_start).map(Some)
         }
     }
 
@@ -730,8 +1578,7 @@ impl Memory {
         series: Series<I>,
         pos: Offset,
     ) -> Result<&[I], MemoryError> {
-        let block = self.get::<Block>(series.address)?;
-        let len = block.len;
+        let len = self.len(series)?;
         if pos >= len {
             Err(MemoryError::OutOfBounds)
         } else {
@@ -739,44 +1586,9 @@ impl Memory {
         }
     }
 
-    // pub fn drain<I: AnyBitPattern + NoUninit>(
-    //     &mut self,
-    //     from: Series<I>,
-    //     pos: Offset,
-    // ) -> Result<Series<I>, MemoryError> {
-    //     let item_size = std::mem::size_of::<I>() as Offset;
-
-    //     let from_block = self.get_mut::<Block>(from.address)?;
-    //     let from_len = from_block.len;
-    //     let copy_len = from_len - pos;
-    //     from_block.len = pos;
-
-    //     let copy_bytes = copy_len * item_size;
-    //     let to_address = self.heap_alloc(copy_bytes, copy_len)?;
-
-    //     let start = from.address + Block::SIZE + (pos * item_size);
-    //     let end = start + copy_bytes;
-    //     let start = start as usize;
-    //     let end = end as usize;
-
-    //     if end > self.memory.len() {
-    //         return Err(MemoryError::OutOfBounds);
-    //     }
-
-    //     let dst = to_address + Block::SIZE;
-    //     let dst = dst as usize;
-    //     let copy_bytes = copy_bytes as usize;
-
-    //     if dst > self.memory.len() - copy_bytes {
-    //         return Err(MemoryError::OutOfBounds);
-    //     }
-
-    //     self.memory.copy_within(start..end, dst);
-    //     Ok(Series::new(to_address))
-    // }
-
     pub fn drop<I>(&mut self, series: Series<I>, items: Offset) -> Result<(), MemoryError> {
-        let block = self.get_mut::<Block>(series.address)?;
+        let address = self.resolve(series.address)?;
+        let block = self.get_mut::<Block>(address)?;
         let new_len = block
             .len
             .checked_sub(items)
@@ -790,8 +1602,7 @@ impl Memory {
         series: Series<I>,
         items: Offset,
     ) -> Result<(), MemoryError> {
-        let block = self.get::<Block>(series.address)?;
-        let len = block.len;
+        let len = self.len(series)?;
         if len == 0 {
             Err(MemoryError::StackUnderflow)
         } else {
@@ -808,135 +1619,506 @@ impl Memory {
                 .ok_or(MemoryError::StackUnderflow)?;
             *new_last_item = last;
 
-            let block = self.get_mut::<Block>(series.address)?;
+            let address = self.resolve(series.address)?;
+            let block = self.get_mut::<Block>(address)?;
             block.len = (new_last + 1) as Offset;
             Ok(())
         }
     }
 
-    pub fn get_or_add_symbol(&mut self, symbol: &str) -> Result<Series<u8>, MemoryError> {
-        let header = self.get_mut::<MemHeader>(0)?;
-        let symbol_table = header.symbol_table;
+    /// Sets the length of the series, growing it as needed. Items past the
+    /// previous length are left as they are, callers overwrite them.
+    fn resize<I>(&mut self, series: Series<I>, new_len: Offset) -> Result<(), MemoryError> {
+        let mut address = self.resolve(series.address)?;
+        let block = self.get::<Block>(address)?;
+        let item_size = std::mem::size_of::<I>() as Offset;
+        let cap_items = (block.cap - Block::SIZE) / item_size;
+        if new_len > cap_items {
+            address = self.grow_series(series, new_len)?;
+        }
+        self.get_mut::<Block>(address)?.len = new_len;
+        Ok(())
+    }
+
+    /// Checks that `range` lies within the items of the series, and returns
+    /// its length.
+    fn check_range<I>(
+        &self,
+        series: Series<I>,
+        range: &Range<Offset>,
+    ) -> Result<Offset, MemoryError> {
+        let len = self.len(series)?;
+        if range.start > range.end || range.end > len {
+            Err(MemoryError::OutOfBounds)
+        } else {
+            Ok(len)
+        }
+    }
 
-        let size_of_symbol = std::mem::size_of::<Address>() as Offset;
+    /// Drops the code compiled from a block whose items are about to change,
+    /// so that it is compiled again from the new items when next evaluated.
+    /// The cache is kept in the original header, past any forwarding.
+    fn drop_code<I>(&mut self, series: Series<I>) -> Result<(), MemoryError> {
+        self.get_mut::<Block>(series.address)?.bindings = 0;
+        Ok(())
+    }
 
-        let block = self.get::<Block>(symbol_table)?;
-        let cap = (block.cap - Block::SIZE) / size_of_symbol;
-        if cap == 0 {
-            return Err(MemoryError::OutOfMemory);
+    /// Inserts `values` before the item at `index`, appending them when
+    /// `index` is the length of the series. The series grows as needed.
+    pub fn insert<I: AnyBitPattern + NoUninit>(
+        &mut self,
+        series: Series<I>,
+        index: Offset,
+        values: &[I],
+    ) -> Result<(), MemoryError> {
+        let len = self.check_range(series, &(index..index))?;
+        let count = values.len() as Offset;
+        self.drop_code(series)?;
+        self.resize(series, len + count)?;
+        let items = self.get_items_slice_mut(series, index..len + count)?;
+        items.copy_within(..(len - index) as usize, count as usize);
+        items[..count as usize].copy_from_slice(values);
+        Ok(())
+    }
+
+    /// Removes the items in `range`, moving the following ones down.
+    pub fn remove<I: AnyBitPattern + NoUninit>(
+        &mut self,
+        series: Series<I>,
+        range: Range<Offset>,
+    ) -> Result<(), MemoryError> {
+        let len = self.check_range(series, &range)?;
+        let count = range.end - range.start;
+        self.drop_code(series)?;
+        let items = self.get_items_slice_mut(series, range.start..len)?;
+        items.copy_within(count as usize.., 0);
+        self.resize(series, len - count)
+    }
+
+    /// Overwrites the items from `index` on with `values`, extending the
+    /// series if they go past its end.
+    pub fn change<I: AnyBitPattern + NoUninit>(
+        &mut self,
+        series: Series<I>,
+        index: Offset,
+        values: &[I],
+    ) -> Result<(), MemoryError> {
+        let len = self.check_range(series, &(index..index))?;
+        let end = index + values.len() as Offset;
+        self.drop_code(series)?;
+        if end > len {
+            self.resize(series, end)?;
         }
+        self.get_items_slice_mut(series, index..end)?
+            .copy_from_slice(values);
+        Ok(())
+    }
 
-        let hash_code = {
-            let mut hasher = DefaultHasher::new();
-            symbol.hash(&mut hasher);
-            hasher.finish() as u32
-        };
+    /// Copies the items in `range` into a new series.
+    pub fn copy_part<I: AnyBitPattern + NoUninit>(
+        &mut self,
+        series: Series<I>,
+        range: Range<Offset>,
+    ) -> Result<Series<I>, MemoryError> {
+        self.check_range(series, &range)?;
+        let items = self.get_items_slice(series, range)?.to_vec();
+        self.alloc_items(&items)
+    }
 
-        let start = hash_code % cap;
-        let mut idx = start;
-        loop {
-            let item = self.get::<Address>(symbol_table + Block::SIZE + idx * size_of_symbol)?;
-            if *item == 0 {
-                let string = self.alloc_string(symbol)?;
-                let item =
-                    self.get_mut::<Address>(symbol_table + Block::SIZE + idx * size_of_symbol)?;
-                *item = string.address();
+    /// Reverses the order of the items in `range`.
+    pub fn reverse<I: AnyBitPattern + NoUninit>(
+        &mut self,
+        series: Series<I>,
+        range: Range<Offset>,
+    ) -> Result<(), MemoryError> {
+        self.check_range(series, &range)?;
+        self.drop_code(series)?;
+        self.get_items_slice_mut(series, range)?.reverse();
+        Ok(())
+    }
+
+    /// Returns the length of the series of a series value in items,
+    /// regardless of its position. Any string counts bytes.
+    fn series_len(&self, value: Value) -> Result<Offset, MemoryError> {
+        if value.is_series() {
+            self.len(Series::<u8>::new(value.data()))
+        } else {
+            Err(MemoryError::TypeMismatch)
+        }
+    }
+
+    /// Returns the offset in the items of its series of the position of a
+    /// series value: the position itself, or for any string the start of
+    /// the character at that byte offset.
+    pub fn item_offset(&self, value: Value) -> Result<Offset, MemoryError> {
+        if value.is_any_string() {
+            let text = self.get_string(value.as_any_string()?)?;
+            Ok(char_boundary(text, value.index()) as Offset)
+        } else {
+            self.series_len(value)?;
+            Ok(value.index())
+        }
+    }
+
+    /// Returns the 0-based position of a series value, which for any string
+    /// counts the characters before it.
+    pub fn index_of(&self, value: Value) -> Result<Offset, MemoryError> {
+        if value.is_any_string() {
+            let text = self.get_string(value.as_any_string()?)?;
+            let at = char_boundary(text, value.index());
+            Ok(text[..at].chars().count() as Offset)
+        } else {
+            self.series_len(value)?;
+            Ok(value.index())
+        }
+    }
 
-                let block = self.get_mut::<Block>(symbol_table)?;
-                block.len += 1;
+    /// Returns the series value at its head
+    pub fn head(&self, value: Value) -> Result<Value, MemoryError> {
+        self.series_len(value)?;
+        value.with_index(0)
+    }
 
-                return Ok(string);
+    /// Returns the series value just past its last item
+    pub fn tail(&self, value: Value) -> Result<Value, MemoryError> {
+        value.with_index(self.series_len(value)?)
+    }
+
+    /// Returns the series value moved by `offset` items, or characters of
+    /// any string, clamped to its head and tail.
+    pub fn skip(&self, value: Value, offset: i64) -> Result<Value, MemoryError> {
+        if value.is_any_string() {
+            // walk only the characters skipped, from the position
+            let text = self.get_string(value.as_any_string()?)?;
+            let at = char_boundary(text, value.index());
+            let count = offset.unsigned_abs().min(usize::MAX as u64) as usize;
+            let index = if offset >= 0 {
+                text[at..]
+                    .char_indices()
+                    .nth(count)
+                    .map_or(text.len(), |(next, _)| at + next)
             } else {
-                let string = self.get_string(Series::new(*item))?;
-                if string == symbol {
-                    return Ok(Series::new(*item));
-                }
-                idx += 1;
-                if idx >= cap {
-                    idx = 0;
-                }
-                if idx == start {
-                    return Err(MemoryError::OutOfMemory);
-                }
-            }
+                text[..at]
+                    .char_indices()
+                    .nth_back(count - 1)
+                    .map_or(0, |(back, _)| back)
+            };
+            return value.with_index(index as Offset);
         }
+        let len = self.series_len(value)? as i64;
+        let index = (value.index() as i64).saturating_add(offset).clamp(0, len);
+        value.with_index(index as Offset)
     }
 
-    const PHI: u32 = 0x9e3779b9;
+    pub fn next(&self, value: Value) -> Result<Value, MemoryError> {
+        self.skip(value, 1)
+    }
 
-    pub fn get_word(&self, symbol: Address) -> Result<Value, MemoryError> {
-        const KV_SIZE: Offset = std::mem::size_of::<KeyValue>() as Offset;
+    pub fn back(&self, value: Value) -> Result<Value, MemoryError> {
+        self.skip(value, -1)
+    }
 
-        let header = self.get::<MemHeader>(0)?;
-        let system_words = header.system_words;
+    /// Returns the series value at the 1-based `index` from its position, as
+    /// REBOL's `at`: 1 is the current position, 0 the previous one.
+    pub fn at(&self, value: Value, index: i64) -> Result<Value, MemoryError> {
+        self.skip(value, index.saturating_sub(1))
+    }
+
+    pub fn is_head(&self, value: Value) -> Result<bool, MemoryError> {
+        Ok(self.item_offset(value)? == 0)
+    }
+
+    /// Returns true if the series value has no items from its position on,
+    /// including when the series was shortened past its position.
+    pub fn is_tail(&self, value: Value) -> Result<bool, MemoryError> {
+        Ok(self.item_offset(value)? >= self.series_len(value)?)
+    }
+
+    /// Returns the number of items, or characters of any string, from the
+    /// position of a series value to its tail.
+    pub fn length_of(&self, value: Value) -> Result<Offset, MemoryError> {
+        if value.is_any_string() {
+            return Ok(self.string_at(value)?.chars().count() as Offset);
+        }
+        Ok(self.series_len(value)?.saturating_sub(value.index()))
+    }
+
+    /// Returns the range of the items of its series from the position of a
+    /// series value to its tail, in bytes for any string.
+    pub fn item_range(&self, value: Value) -> Result<Range<Offset>, MemoryError> {
+        let start = self.item_offset(value)?;
+        let len = self.len(Series::<u8>::new(value.data()))?;
+        Ok(start.min(len)..len)
+    }
+
+    /// Returns the items of any block value from its position on
+    pub fn items_at(&self, value: Value) -> Result<&[Value], MemoryError> {
+        if !value.is_any_block() {
+            return Err(MemoryError::TypeMismatch);
+        }
+        let series = Series::<Value>::new(value.data());
+        let len = self.len(series)?;
+        self.get_items_slice(series, value.index().min(len)..len)
+    }
 
-        let block = self.get::<Block>(system_words)?;
-        let cap = (block.cap - Block::SIZE) / KV_SIZE;
+    /// Returns the bytes of a binary or any string value from its position
+    /// on
+    pub fn bytes_at(&self, value: Value) -> Result<&[u8], MemoryError> {
+        if value.is_any_string() {
+            return self.string_at(value).map(str::as_bytes);
+        }
+        if !value.is_binary() {
+            return Err(MemoryError::TypeMismatch);
+        }
+        let series = Series::<u8>::new(value.data());
+        let len = self.len(series)?;
+        self.get_items_slice(series, value.index().min(len)..len)
+    }
+
+    /// Returns the text of any string value from its position on
+    pub fn string_at(&self, value: Value) -> Result<&str, MemoryError> {
+        let text = self.get_string(value.as_any_string()?)?;
+        Ok(&text[char_boundary(text, value.index())..])
+    }
+
+    /// Returns the entry for `hash` in the open-addressing hash table at
+    /// `table`: the first entry `matches` accepts, or else the empty entry
+    /// the probe sequence stops at. The flag tells which one was found.
+    fn probe<I: TableEntry>(
+        &self,
+        table: Address,
+        hash: u32,
+        matches: impl Fn(&Self, &I) -> Result<bool, MemoryError>,
+    ) -> Result<(Address, bool), MemoryError> {
+        let item_size = std::mem::size_of::<I>() as Offset;
+        let block = self.get::<Block>(table)?;
+        let cap = (block.cap - Block::SIZE) / item_size;
         if cap == 0 {
-            return Err(MemoryError::WordNotFound);
+            return Err(MemoryError::OutOfMemory);
         }
 
-        let hash_code = symbol.wrapping_mul(Self::PHI);
-        let start = hash_code % cap;
+        let start = hash % cap;
         let mut idx = start;
         loop {
-            let offset = system_words + Block::SIZE + idx * KV_SIZE;
-            let item = self.get::<KeyValue>(offset)?;
-            if item.key == symbol {
-                return Ok(item.value);
-            } else if item.key == 0 {
-                return Err(MemoryError::WordNotFound);
-            } else {
-                idx += 1;
-                if idx >= cap {
-                    idx = 0;
-                }
-                if idx == start {
-                    return Err(MemoryError::WordNotFound);
-                }
+            let address = table + Block::SIZE + idx * item_size;
+            let item = self.get::<I>(address)?;
+            if item.key() == 0 {
+                return Ok((address, false));
+            } else if matches(self, item)? {
+                return Ok((address, true));
+            }
+            idx += 1;
+            if idx >= cap {
+                idx = 0;
+            }
+            if idx == start {
+                return Err(MemoryError::OutOfMemory);
             }
         }
     }
 
-    pub fn bind_word(&mut self, symbol: Address, create: bool) -> Result<Address, MemoryError> {
-        const KV_SIZE: Offset = std::mem::size_of::<KeyValue>() as Offset;
+    /// Returns the table to insert a new entry for `hash` into, and the
+    /// empty entry to use. Once the table is 3/4 full, its entries are first
+    /// rehashed with `rehash` into a new table of twice the capacity, and
+    /// the old table is released: the caller must store the new address.
+    fn insertion_slot<I: TableEntry>(
+        &mut self,
+        table: Address,
+        hash: u32,
+        rehash: impl Fn(&Self, &I) -> Result<u32, MemoryError>,
+    ) -> Result<(Address, Address), MemoryError> {
+        let item_size = std::mem::size_of::<I>() as Offset;
+        let block = self.get::<Block>(table).copied()?;
+        let cap = (block.cap - Block::SIZE) / item_size;
+        if (block.len + 1) * 4 <= cap * 3 {
+            let (slot, _) = self.probe::<I>(table, hash, |_, _| Ok(false))?;
+            return Ok((table, slot));
+        }
 
-        let header = self.get_mut::<MemHeader>(0)?;
-        let system_words = header.system_words;
+        let entries: Vec<I> = self
+            .get_table(Series::<I>::new(table))?
+            .iter()
+            .filter(|item| item.key() != 0)
+            .copied()
+            .collect();
+        let hashes = entries
+            .iter()
+            .map(|item| rehash(self, item))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let new = self.alloc::<I>((cap * 2).max(4) as usize)?.address;
+        for (item, hash) in entries.into_iter().zip(hashes) {
+            let (slot, _) = self.probe::<I>(new, hash, |_, _| Ok(false))?;
+            *self.get_mut::<I>(slot)? = item;
+        }
+        self.get_mut::<Block>(new)?.len = block.len;
+        self.release(table, block.cap)?;
 
-        let block = self.get::<Block>(system_words)?;
-        let cap = (block.cap - Block::SIZE) / KV_SIZE;
-        if cap == 0 {
-            return Err(MemoryError::OutOfMemory);
+        let (slot, _) = self.probe::<I>(new, hash, |_, _| Ok(false))?;
+        Ok((new, slot))
+    }
+
+    pub fn get_or_add_symbol(&mut self, symbol: &str) -> Result<Series<u8>, MemoryError> {
+        let symbol_table = self.get::<MemHeader>(0)?.symbol_table;
+        let hash_code = symbol_hash(symbol);
+
+        let (slot, found) = self.probe(symbol_table, hash_code, |memory, item: &Address| {
+            Ok(memory.get_string(Series::new(*item))? == symbol)
+        })?;
+        if found {
+            return self.get::<Address>(slot).map(|item| Series::new(*item));
         }
 
+        let (symbol_table, slot) =
+            self.insertion_slot(symbol_table, hash_code, |memory, item: &Address| {
+                Ok(symbol_hash(memory.get_string(Series::new(*item))?))
+            })?;
+        self.get_mut::<MemHeader>(0)?.symbol_table = symbol_table;
+
+        let string = self.alloc_string(symbol)?;
+        *self.get_mut::<Address>(slot)? = string.address();
+        self.get_mut::<Block>(symbol_table)?.len += 1;
+        Ok(string)
+    }
+
+    const PHI: u32 = 0x9e3779b9;
+
+    fn find_system_word(&self, symbol: Address) -> Result<Option<Address>, MemoryError> {
+        let system_words = self.get::<MemHeader>(0)?.system_words;
         let hash_code = symbol.wrapping_mul(Self::PHI);
-        let start = hash_code % cap;
-        let mut idx = start;
-        loop {
-            let offset = system_words + Block::SIZE + idx * KV_SIZE;
-            let item = self.get_mut::<KeyValue>(offset)?;
-            if item.key == symbol {
-                return Ok(offset + 4);
-            } else if item.key == 0 {
-                if !create {
-                    return Err(MemoryError::WordNotFound);
-                }
-                item.key = symbol;
-                item.value = Value::VALUE_NONE;
-                let block = self.get_mut::<Block>(system_words)?;
-                block.len += 1;
-                return Ok(offset + 4);
-            } else {
-                idx += 1;
-                if idx >= cap {
-                    idx = 0;
-                }
-                if idx == start {
-                    return Err(MemoryError::OutOfMemory);
-                }
+        let (slot, found) = self.probe(system_words, hash_code, |_, item: &SystemWord| {
+            Ok(item.key == symbol)
+        })?;
+        if found {
+            self.get::<SystemWord>(slot).map(|item| Some(item.cell))
+        } else {
+            Ok(None)
+        }
+    }
+
+    pub fn get_word(&self, symbol: Address) -> Result<Value, MemoryError> {
+        let cell = self
+            .find_system_word(symbol)?
+            .ok_or(MemoryError::WordNotFound)?;
+        self.get::<Value>(cell).copied()
+    }
+
+    /// Binds `symbol` in the system words, creating it if `create` is set,
+    /// and returns the address of its value cell. The cell never moves, even
+    /// when the system words table is rehashed.
+    pub fn bind_word(&mut self, symbol: Address, create: bool) -> Result<Address, MemoryError> {
+        if let Some(cell) = self.find_system_word(symbol)? {
+            return Ok(cell);
+        } else if !create {
+            return Err(MemoryError::WordNotFound);
+        }
+
+        let system_words = self.get::<MemHeader>(0)?.system_words;
+        let hash_code = symbol.wrapping_mul(Self::PHI);
+        let (system_words, slot) =
+            self.insertion_slot(system_words, hash_code, |_, item: &SystemWord| {
+                Ok(item.key.wrapping_mul(Self::PHI))
+            })?;
+        self.get_mut::<MemHeader>(0)?.system_words = system_words;
+
+        let cell = self.alloc_struct(Value::VALUE_NONE)?;
+        *self.get_mut::<SystemWord>(slot)? = SystemWord { key: symbol, cell };
+        self.get_mut::<Block>(system_words)?.len += 1;
+        Ok(cell)
+    }
+
+    /// Allocates an empty context with room for `words` words.
+    ///
+    /// A context is a series of `KeyValue` searched linearly; the `bindings`
+    /// field of its Block header links the parent context. A parent of 0
+    /// stands for the system words table, which is the root of every chain.
+    pub fn alloc_context(
+        &mut self,
+        parent: Address,
+        words: usize,
+    ) -> Result<Series<KeyValue>, MemoryError> {
+        let context = self.alloc::<KeyValue>(words)?;
+        let block = self.get_mut::<Block>(context.address)?;
+        block.bindings = parent;
+        Ok(context)
+    }
+
+    pub fn context_parent(&self, context: Series<KeyValue>) -> Result<Address, MemoryError> {
+        self.get::<Block>(context.address)
+            .map(|block| block.bindings)
+    }
+
+    /// Adds `symbol` to the context and returns the address of its value slot.
+    ///
+    /// The context grows as needed, which moves its slots: words must be
+    /// added before any code bound to the context is compiled.
+    pub fn add_context_word(
+        &mut self,
+        context: Series<KeyValue>,
+        symbol: Address,
+    ) -> Result<Address, MemoryError> {
+        let kv = KeyValue {
+            key: symbol,
+            value: Value::VALUE_NONE,
+        };
+        self.push(context, kv).map(|address| address + 4)
+    }
+
+    /// Returns the address of the value slot at `index` in the context.
+    pub fn context_slot(
+        &self,
+        context: Series<KeyValue>,
+        index: Offset,
+    ) -> Result<Address, MemoryError> {
+        if index >= self.len(context)? {
+            Err(MemoryError::OutOfBounds)
+        } else {
+            let address = self.resolve(context.address)?;
+            Ok(address + Block::SIZE + index * KeyValue::SIZE + 4)
+        }
+    }
+
+    fn find_in_context(
+        &self,
+        context: Series<KeyValue>,
+        symbol: Address,
+    ) -> Result<Option<Address>, MemoryError> {
+        let address = self.resolve(context.address)?;
+        let items = self.get_items(context)?;
+        let found = items.iter().position(|kv| kv.key == symbol);
+        Ok(found.map(|i| address + Block::SIZE + i as Offset * KeyValue::SIZE + 4))
+    }
+
+    /// Binds `symbol` in `context`, falling back to enclosing contexts and
+    /// finally to the system words, where it is created if `create` is set.
+    pub fn bind_word_in(
+        &mut self,
+        context: Address,
+        symbol: Address,
+        create: bool,
+    ) -> Result<Address, MemoryError> {
+        let mut current = context;
+        while current != 0 {
+            let context = Series::new(current);
+            if let Some(address) = self.find_in_context(context, symbol)? {
+                return Ok(address);
+            }
+            current = self.context_parent(context)?;
+        }
+        self.bind_word(symbol, create)
+    }
+
+    /// Looks up the value of `symbol` the same way `bind_word_in` binds it.
+    pub fn get_word_in(&self, context: Address, symbol: Address) -> Result<Value, MemoryError> {
+        let mut current = context;
+        while current != 0 {
+            let context = Series::new(current);
+            if let Some(address) = self.find_in_context(context, symbol)? {
+                return self.get::<Value>(address).copied();
             }
+            current = self.context_parent(context)?;
         }
+        self.get_word(symbol)
     }
 
     pub fn set_word_str(&mut self, symbol: &str, value: Value) -> Result<(), MemoryError> {
@@ -966,6 +2148,274 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_memory_grows() -> Result<(), MemoryError> {
+        let mut memory = Memory::with_limit(0, 64 * 1024)?;
+        let initial = memory.size();
+
+        let first = memory.alloc_string("first")?;
+        let series = memory.alloc::<Value>(1000)?;
+        assert!(memory.size() > initial);
+        assert!(memory.size() <= memory.limit());
+        memory.push(series, Value::int(42))?;
+
+        assert_eq!(memory.get_string(first)?, "first");
+        assert_eq!(memory.get_items(series)?, &[Value::int(42)]);
+
+        assert!(matches!(
+            memory.alloc::<Value>(8 * 1024),
+            Err(MemoryError::OutOfMemory)
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_series_grows() -> Result<(), MemoryError> {
+        let mut memory = Memory::new(65536)?;
+        let series = memory.alloc::<Value>(2)?;
+        let block = Value::block(series);
+        for i in 0..100 {
+            memory.push(series, Value::int(i))?;
+        }
+        memory.push_all(series, &[Value::int(100), Value::int(101)])?;
+
+        let items = memory.get_items(block.as_block()?)?;
+        assert_eq!(items.len(), 102);
+        assert!(
+            items
+                .iter()
+                .enumerate()
+                .all(|(i, v)| *v == Value::int(i as i64))
+        );
+        assert_eq!(memory.pop(series)?, Value::int(101));
+        assert_eq!(memory.get_item(series, 50)?, &Value::int(50));
+
+        let string = memory.alloc_string("abc")?;
+        memory.push_all(string, b"defghijklmnop")?;
+        assert_eq!(memory.get_string(string)?, "abcdefghijklmnop");
+
+        // the released space is reused, and the heap stays walkable
+        let small = memory.alloc_string("x")?;
+        assert!(small.address() < string.address());
+        assert!(memory.allocations().is_ok());
+        Ok(())
+    }
+
+    #[test]
+    fn test_image_roundtrip() -> Result<(), MemoryError> {
+        let path = std::env::temp_dir().join(format!("rebel-mem-{}.img", std::process::id()));
+        let mut memory = Memory::new(65536)?;
+        let string = memory.alloc_string("persistent")?;
+        memory.set_word_str("s", Value::string(string))?;
+        memory.save_image(&path)?;
+
+        let mut loaded = Memory::load_image(&path)?;
+        std::fs::remove_file(&path)?;
+        let s = loaded.get_or_add_symbol("s")?;
+        let string = loaded.get_word(s.address())?.as_string()?;
+        assert_eq!(loaded.get_string(string)?, "persistent");
+        loaded.alloc_string("still allocates")?;
+
+        assert!(matches!(
+            Memory::from_image(vec![0; 64], Memory::MAX_SIZE),
+            Err(MemoryError::InvalidImage)
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_image_version() -> Result<(), MemoryError> {
+        let memory = Memory::new(0)?;
+        let mut image = memory.memory.clone();
+        image.truncate(memory.get::<MemHeader>(0)?.heap_top as usize);
+        assert_eq!(&image[..8], &[0xEF, 0xBE, 0xAD, 0xDE, 4, 0, 0, 0]);
+
+        assert!(Memory::from_image(image.clone(), Memory::MAX_SIZE).is_ok());
+        image[4] = 3;
+        assert!(matches!(
+            Memory::from_image(image, Memory::MAX_SIZE),
+            Err(MemoryError::UnsupportedVersion(3))
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_symbol_hash_is_stable() {
+        assert_eq!(symbol_hash(""), 0x811c9dc5);
+        assert_eq!(symbol_hash("a"), 0xe40c292c);
+        assert_eq!(symbol_hash("foobar"), 0xbf9cf968);
+    }
+
+    #[test]
+    fn test_context_lookup() -> Result<(), MemoryError> {
+        let mut memory = Memory::new(65536)?;
+        let x = memory.get_or_add_symbol("x")?.address();
+        let y = memory.get_or_add_symbol("y")?.address();
+        memory.set_word(x, Value::int(1))?;
+        memory.set_word(y, Value::int(2))?;
+
+        let outer = memory.alloc_context(0, 1)?;
+        let outer_x = memory.add_context_word(outer, x)?;
+        *memory.get_mut::<Value>(outer_x)? = Value::int(10);
+
+        let inner = memory.alloc_context(outer.address(), 1)?;
+        memory.add_context_word(inner, y)?;
+
+        assert_eq!(memory.get_word_in(inner.address(), x)?, Value::int(10));
+        assert_eq!(memory.get_word_in(inner.address(), y)?, Value::VALUE_NONE);
+        assert_eq!(memory.get_word_in(outer.address(), y)?, Value::int(2));
+        assert_eq!(memory.get_word(x)?, Value::int(1));
+        assert_eq!(memory.bind_word_in(inner.address(), x, false)?, outer_x);
+        assert_eq!(memory.context_slot(outer, 0)?, outer_x);
+
+        let z = memory.get_or_add_symbol("z")?.address();
+        assert!(matches!(
+            memory.bind_word_in(inner.address(), z, false),
+            Err(MemoryError::WordNotFound)
+        ));
+        let global_z = memory.bind_word_in(inner.address(), z, true)?;
+        assert_eq!(memory.bind_word(z, false)?, global_z);
+        Ok(())
+    }
+
+    #[test]
+    fn test_tables_grow() -> Result<(), MemoryError> {
+        let mut memory = Memory::new(65536)?;
+        let first = memory.get_or_add_symbol("word0")?;
+        let cell = memory.bind_word(first.address(), true)?;
+        let tables = memory.header_roots()?;
+
+        for i in 0..3000 {
+            memory.set_word_str(&format!("word{i}"), Value::int(i))?;
+        }
+
+        let grown = memory.header_roots()?;
+        assert_ne!(grown.symbol_table.address(), tables.symbol_table.address());
+        assert_ne!(grown.system_words.address(), tables.system_words.address());
+        assert_eq!(memory.len(grown.symbol_table)?, 3000);
+        assert_eq!(memory.bind_word(first.address(), false)?, cell);
+        assert_eq!(*memory.get::<Value>(cell)?, Value::int(0));
+        for i in [1, 767, 768, 2999] {
+            let symbol = memory.get_or_add_symbol(&format!("word{i}"))?;
+            assert_eq!(memory.get_word(symbol.address())?, Value::int(i));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_allocation_accounting() -> Result<(), MemoryError> {
+        let mut memory = Memory::new(65536)?;
+        memory.alloc_string("nobody")?;
+        assert_eq!(memory.set_owner(1), 0);
+
+        let series = memory.alloc::<Value>(1)?;
+        for i in 0..8 {
+            memory.push(series, Value::int(i))?;
+        }
+        memory.alloc_string("garbage")?;
+        let used = memory.allocations()?.last().map_or(0, |a| a.0 + a.1);
+        let start = series.address();
+        let held = memory.allocated(1);
+        // everything from the series on, minus the data areas it released
+        assert!(held > 0 && held < used - start);
+
+        memory.set_quota(1, Some(held + 16));
+        memory.alloc_string("fits")?;
+        assert!(matches!(
+            memory.alloc::<Value>(4),
+            Err(MemoryError::QuotaExceeded)
+        ));
+        memory.set_owner(2);
+        memory.alloc::<Value>(4)?;
+
+        let mut roots = crate::gc::Roots::default();
+        roots.value(Value::block(series));
+        memory.collect_garbage(&roots)?;
+        assert!(memory.allocated(1) < held);
+        assert_eq!(memory.allocated(2), 0);
+        Ok(())
+    }
+
+    #[test]
+    fn test_series_positions() -> Result<(), MemoryError> {
+        let mut memory = Memory::new(65536)?;
+        let items = [Value::int(1), Value::int(2), Value::int(3)];
+        let block = Value::block(memory.alloc_items(&items)?);
+
+        let second = memory.next(block)?;
+        assert_eq!(second.index(), 1);
+        assert_eq!(second.kind(), Value::BLOCK);
+        assert_eq!(memory.items_at(second)?, &items[1..]);
+        assert_eq!(memory.back(second)?, block);
+        assert_eq!(memory.at(second, 2)?.index(), 2);
+        assert_eq!(memory.at(second, 0)?, block);
+        assert_eq!(memory.skip(block, 10)?, memory.tail(block)?);
+        assert_eq!(memory.skip(second, -10)?, memory.head(second)?);
+        assert!(memory.is_tail(memory.tail(block)?)?);
+        assert!(memory.is_head(block)? && !memory.is_head(second)?);
+        assert_eq!(memory.length_of(second)?, 2);
+
+        let string = Value::string(memory.alloc_string("rebel")?);
+        assert_eq!(memory.string_at(memory.skip(string, 2)?)?, "bel");
+
+        let text = Value::string(memory.alloc_string("\u{e9}t\u{e9}")?);
+        assert_eq!(memory.length_of(text)?, 3);
+        let second = memory.next(text)?;
+        assert_eq!(memory.string_at(second)?, "t\u{e9}");
+        assert_eq!(memory.bytes_at(second)?, "t\u{e9}".as_bytes());
+        assert_eq!(memory.item_offset(second)?, 2);
+        assert_eq!(memory.index_of(second)?, 1);
+        assert_eq!(memory.item_range(memory.tail(text)?)?, 5..5);
+        assert_eq!(memory.skip(memory.tail(text)?, -2)?, second);
+        assert_eq!(
+            memory.string_at(memory.back(memory.tail(text)?)?)?,
+            "\u{e9}"
+        );
+        assert_eq!(memory.skip(text, 10)?, memory.tail(text)?);
+        // a position left inside a character by an edit is at its start
+        let inside = text.with_index(1)?;
+        assert!(memory.is_head(inside)?);
+        assert_eq!(memory.string_at(inside)?, "\u{e9}t\u{e9}");
+        assert!(matches!(
+            memory.next(Value::int(1)),
+            Err(MemoryError::TypeMismatch)
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_series_editing() -> Result<(), MemoryError> {
+        let mut memory = Memory::new(65536)?;
+        let series = memory.alloc_items(&[1u32, 2, 3])?;
+
+        memory.insert(series, 1, &[7, 8, 9, 10])?;
+        assert_eq!(memory.get_items(series)?, &[1, 7, 8, 9, 10, 2, 3]);
+        memory.remove(series, 2..4)?;
+        assert_eq!(memory.get_items(series)?, &[1, 7, 10, 2, 3]);
+        memory.change(series, 3, &[4, 5, 6])?;
+        assert_eq!(memory.get_items(series)?, &[1, 7, 10, 4, 5, 6]);
+        memory.reverse(series, 1..6)?;
+        assert_eq!(memory.get_items(series)?, &[1, 6, 5, 4, 10, 7]);
+        let part = memory.copy_part(series, 2..4)?;
+        assert_eq!(memory.get_items(part)?, &[5, 4]);
+        memory.insert(series, 6, &[0])?;
+        assert_eq!(memory.get_items(series)?, &[1, 6, 5, 4, 10, 7, 0]);
+
+        assert!(matches!(
+            memory.insert(series, 8, &[0]),
+            Err(MemoryError::OutOfBounds)
+        ));
+        assert!(matches!(
+            memory.remove(series, 5..8),
+            Err(MemoryError::OutOfBounds)
+        ));
+        assert!(matches!(
+            memory.copy_part(series, 6..9),
+            Err(MemoryError::OutOfBounds)
+        ));
+        Ok(())
+    }
+
     // #[test]
     // fn test_memory_push_pop() {
     //     let mut memory = Memory::new(1024).unwrap();
@@ -0,0 +1,326 @@
+// Rebel™ © 2025 Huly Labs • https://hulylabs.com • SPDX-License-Identifier: MIT
+
+//! Exact fixed-point amounts for the money type
+//!
+//! A [`Money`] is a signed count of ten-thousandths, so amounts like
+//! `$12.34` are stored exactly and sums never pick up binary rounding
+//! errors. Products, quotients and conversions from floats that need more
+//! than four decimal places are rounded with a [`Rounding`] mode chosen by
+//! the caller.
+
+use std::fmt;
+use std::str::FromStr;
+use thiserror::Error;
+
+#[derive(Debug, Error, PartialEq)]
+pub enum MoneyError {
+    #[error("money overflow")]
+    Overflow,
+    #[error("money has more than 4 decimal places")]
+    Precision,
+    #[error("invalid money")]
+    Invalid,
+    #[error("division by zero")]
+    DivisionByZero,
+}
+
+/// How a result is brought to four decimal places
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Rounding {
+    /// To the nearest, ties to the even neighbour (banker's rounding)
+    #[default]
+    HalfEven,
+    /// To the nearest, ties away from zero
+    HalfUp,
+    /// Toward zero
+    Down,
+    /// Away from zero
+    Up,
+    /// Toward negative infinity
+    Floor,
+    /// Toward positive infinity
+    Ceiling,
+}
+
+impl Rounding {
+    /// Divides `n` by `d`, which must not be 0, rounding the quotient.
+    fn divide(self, n: i128, d: i128) -> i128 {
+        let (quotient, remainder) = (n / d, n % d);
+        if remainder == 0 {
+            return quotient;
+        }
+        let negative = (n < 0) != (d < 0);
+        let away = if negative { quotient - 1 } else { quotient + 1 };
+        let half = (remainder.abs() * 2).cmp(&d.abs());
+        let round_away = match self {
+            Rounding::HalfEven => half.is_gt() || (half.is_eq() && quotient % 2 != 0),
+            Rounding::HalfUp => half.is_ge(),
+            Rounding::Down => false,
+            Rounding::Up => true,
+            Rounding::Floor => negative,
+            Rounding::Ceiling => !negative,
+        };
+        if round_away { away } else { quotient }
+    }
+
+    /// Rounds a float to an integral value
+    fn round(self, value: f64) -> f64 {
+        match self {
+            Rounding::HalfEven => value.round_ties_even(),
+            Rounding::HalfUp => value.round(),
+            Rounding::Down => value.trunc(),
+            Rounding::Up if value.fract() != 0.0 => value.trunc() + value.signum(),
+            Rounding::Up => value,
+            Rounding::Floor => value.floor(),
+            Rounding::Ceiling => value.ceil(),
+        }
+    }
+}
+
+/// An exact amount with four decimal places.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Money(i64);
+
+impl Money {
+    /// Number of decimal places kept
+    pub const PLACES: usize = 4;
+    /// Units in 1
+    pub const SCALE: i64 = 10_000;
+
+    /// Creates an amount of `units` ten-thousandths
+    pub fn from_units(units: i64) -> Self {
+        Self(units)
+    }
+
+    /// Returns the amount in ten-thousandths
+    pub fn units(&self) -> i64 {
+        self.0
+    }
+
+    pub fn from_int(value: i64) -> Result<Self, MoneyError> {
+        value
+            .checked_mul(Self::SCALE)
+            .map(Self)
+            .ok_or(MoneyError::Overflow)
+    }
+
+    /// Converts a float, rounding it to four decimal places. Any float
+    /// written with at most four decimal places converts to that exact
+    /// amount while it stays well within the precision of f64.
+    pub fn from_float(value: f64, rounding: Rounding) -> Result<Self, MoneyError> {
+        Self::from_float_units(value * Self::SCALE as f64, rounding)
+    }
+
+    fn from_float_units(units: f64, rounding: Rounding) -> Result<Self, MoneyError> {
+        let units = rounding.round(units);
+        // i64::MAX as f64 rounds up to 2^63, which is out of range
+        if units.is_finite() && units >= i64::MIN as f64 && units < i64::MAX as f64 {
+            Ok(Self(units as i64))
+        } else {
+            Err(MoneyError::Overflow)
+        }
+    }
+
+    pub fn to_float(&self) -> f64 {
+        self.0 as f64 / Self::SCALE as f64
+    }
+
+    pub fn checked_add(self, other: Money) -> Result<Self, MoneyError> {
+        self.0
+            .checked_add(other.0)
+            .map(Self)
+            .ok_or(MoneyError::Overflow)
+    }
+
+    pub fn checked_sub(self, other: Money) -> Result<Self, MoneyError> {
+        self.0
+            .checked_sub(other.0)
+            .map(Self)
+            .ok_or(MoneyError::Overflow)
+    }
+
+    pub fn multiply(self, other: Money, rounding: Rounding) -> Result<Self, MoneyError> {
+        let product = self.0 as i128 * other.0 as i128;
+        Self::from_wide(rounding.divide(product, Self::SCALE as i128))
+    }
+
+    pub fn divide(self, other: Money, rounding: Rounding) -> Result<Self, MoneyError> {
+        if other.0 == 0 {
+            return Err(MoneyError::DivisionByZero);
+        }
+        let dividend = self.0 as i128 * Self::SCALE as i128;
+        Self::from_wide(rounding.divide(dividend, other.0 as i128))
+    }
+
+    /// Multiplies by a float as it is, so a factor below 0.0001 is not
+    /// rounded away before multiplying.
+    pub fn multiply_float(self, factor: f64, rounding: Rounding) -> Result<Self, MoneyError> {
+        Self::from_float_units(self.0 as f64 * factor, rounding)
+    }
+
+    /// Divides by a float as it is, so a divisor too small to be an amount
+    /// is not taken for 0.
+    pub fn divide_float(self, divisor: f64, rounding: Rounding) -> Result<Self, MoneyError> {
+        if divisor == 0.0 {
+            return Err(MoneyError::DivisionByZero);
+        }
+        Self::from_float_units(self.0 as f64 / divisor, rounding)
+    }
+
+    fn from_wide(units: i128) -> Result<Self, MoneyError> {
+        i64::try_from(units)
+            .map(Self)
+            .map_err(|_| MoneyError::Overflow)
+    }
+}
+
+/// Parses `12.34`, `$12.34`, `-$12.34` or `$-12.34`.
+impl FromStr for Money {
+    type Err = MoneyError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (negative, rest) = match s.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, s.strip_prefix('+').unwrap_or(s)),
+        };
+        let rest = rest.strip_prefix('$').unwrap_or(rest);
+        let (negative, rest) = match rest.strip_prefix('-') {
+            Some(rest) if !negative && s.starts_with('$') => (true, rest),
+            _ => (negative, rest),
+        };
+        let (whole, fraction) = rest.split_once('.').unwrap_or((rest, ""));
+        let is_digits = |part: &str| part.bytes().all(|byte| byte.is_ascii_digit());
+        if (whole.is_empty() && fraction.is_empty()) || !is_digits(whole) || !is_digits(fraction) {
+            return Err(MoneyError::Invalid);
+        }
+        if fraction.len() > Self::PLACES {
+            return Err(MoneyError::Precision);
+        }
+        // negative amounts accumulate downwards, so the smallest one parses
+        let digits = whole.bytes().chain(fraction.bytes());
+        let padding = std::iter::repeat_n(b'0', Self::PLACES - fraction.len());
+        digits
+            .chain(padding)
+            .try_fold(0i64, |units, digit| {
+                let digit = (digit - b'0') as i64;
+                let units = units.checked_mul(10)?;
+                if negative {
+                    units.checked_sub(digit)
+                } else {
+                    units.checked_add(digit)
+                }
+            })
+            .map(Self)
+            .ok_or(MoneyError::Overflow)
+    }
+}
+
+/// Formats as `$12.34` or `-$0.125`, with at least two decimal places.
+impl fmt::Display for Money {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let sign = if self.0 < 0 { "-" } else { "" };
+        let units = self.0.unsigned_abs();
+        let scale = Self::SCALE as u64;
+        let fraction = format!("{:04}", units % scale);
+        let fraction = fraction.trim_end_matches('0');
+        write!(f, "{sign}${}.{fraction:0<2}", units / scale)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn money(s: &str) -> Money {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_money_parse_and_display() {
+        assert_eq!(money("$12.34").units(), 123400);
+        assert_eq!(money("12.34"), money("$12.34"));
+        assert_eq!(money("-$0.5").units(), -5000);
+        assert_eq!(money("$-0.5").units(), -5000);
+        assert_eq!(money("$7").units(), 70000);
+        assert_eq!(money("$.0001").units(), 1);
+        assert_eq!(money("-$922337203685477.5808").units(), i64::MIN);
+
+        assert_eq!("$1.23456".parse::<Money>(), Err(MoneyError::Precision));
+        assert_eq!(
+            "$922337203685477.5808".parse::<Money>(),
+            Err(MoneyError::Overflow)
+        );
+        for invalid in ["$", "$.", "1.2.3", "--1", "$1a", "-$-1"] {
+            assert_eq!(
+                invalid.parse::<Money>(),
+                Err(MoneyError::Invalid),
+                "{invalid}"
+            );
+        }
+
+        assert_eq!(money("$12.34").to_string(), "$12.34");
+        assert_eq!(money("-$0.125").to_string(), "-$0.125");
+        assert_eq!(money("$7").to_string(), "$7.00");
+    }
+
+    #[test]
+    fn test_money_arithmetic() -> Result<(), MoneyError> {
+        // 0.1 + 0.2 is exactly 0.3
+        let sum = money("$0.1").checked_add(money("$0.2"))?;
+        assert_eq!(sum, money("$0.3"));
+        assert_eq!(money("$1").checked_sub(money("$0.01"))?, money("$0.99"));
+        assert_eq!(
+            Money::from_units(i64::MAX).checked_add(money("$0.0001")),
+            Err(MoneyError::Overflow)
+        );
+
+        let even = Rounding::HalfEven;
+        assert_eq!(money("$1.5").multiply(money("$2"), even)?, money("$3"));
+        assert_eq!(money("$0.0001").multiply(money("$0.5"), even)?, money("$0"));
+        assert_eq!(money("$10").divide(money("$3"), even)?, money("$3.3333"));
+        assert_eq!(
+            money("$1").divide(money("$0"), even),
+            Err(MoneyError::DivisionByZero)
+        );
+        assert_eq!(money("$3").multiply_float(0.5, even)?, money("$1.5"));
+        assert_eq!(
+            money("$100").multiply_float(0.00001, even)?,
+            money("$0.001")
+        );
+        assert_eq!(
+            money("$1").multiply_float(1e16, even),
+            Err(MoneyError::Overflow)
+        );
+        assert_eq!(money("$3").divide_float(1.5, even)?, money("$2"));
+        assert_eq!(
+            money("$1").divide_float(1e-12, even)?,
+            money("$1000000000000")
+        );
+        assert_eq!(
+            money("$1").divide_float(1e-15, even),
+            Err(MoneyError::Overflow)
+        );
+
+        // $0.00025 and -$0.00035 rounded to four places in every mode
+        let cases = [
+            (Rounding::HalfEven, "$0.0002", "-$0.0004"),
+            (Rounding::HalfUp, "$0.0003", "-$0.0004"),
+            (Rounding::Down, "$0.0002", "-$0.0003"),
+            (Rounding::Up, "$0.0003", "-$0.0004"),
+            (Rounding::Floor, "$0.0002", "-$0.0004"),
+            (Rounding::Ceiling, "$0.0003", "-$0.0003"),
+        ];
+        for (rounding, positive, negative) in cases {
+            let half = money("$0.5");
+            assert_eq!(money("$0.0005").multiply(half, rounding)?, money(positive));
+            assert_eq!(money("-$0.0007").multiply(half, rounding)?, money(negative));
+        }
+
+        assert_eq!(Money::from_float(12.34, even)?, money("$12.34"));
+        assert_eq!(Money::from_float(-0.5, Rounding::Floor)?, money("-$0.5"));
+        assert_eq!(Money::from_float(1e30, even), Err(MoneyError::Overflow));
+        assert_eq!(Money::from_float(f64::NAN, even), Err(MoneyError::Overflow));
+        assert_eq!(Money::from_int(3)?.to_float(), 3.0);
+        Ok(())
+    }
+}
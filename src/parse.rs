@@ -9,42 +9,131 @@
 //! - `parse`: Parses the input exactly as provided
 //! - `parse_block`: Automatically wraps the input in a block
 //!
+//! `parse_recovering` and `parse_block_recovering` do the same, but skip
+//! bad tokens and go on, reporting every error instead of the first one.
+//!
+//! Errors carry the [`Location`] where they occurred, and collectors can
+//! receive the span of every value through [`Collector::span`].
+//!
 //! The parser handles the following REBOL-inspired syntax elements:
 //! - Strings with escape sequences (e.g., `"Hello\nWorld"`)
+//! - Brace strings spanning lines, with nested balanced braces and caret
+//!   escapes (e.g., `{line one^/line {two}}`)
 //! - Different word types:
 //!   - Regular words (e.g., `word`)
 //!   - Set-words with trailing colon (e.g., `word:`)
 //!   - Get-words with leading colon (e.g., `:word`)
+//!   - Lit-words with leading quote (e.g., `'word`)
 //! - Integer literals (e.g., `123`, `-456`, `+789`)
 //! - Float literals (e.g., `3.14`, `-2.5`, `+10.0`)
+//! - Money literals (e.g., `$12.34`, `-$0.5`, `$100`)
+//! - Binary literals in hex or base64 (e.g., `#{DEADBEEF}`, `64#{3q2+7w==}`)
+//! - Date literals (e.g., `2026-10-16`, `16-Oct-2026/10:30:00+2:00`)
+//! - Time literals (e.g., `10:30`, `10:30:15.5`, `-1:30`)
+//! - Tuple literals (e.g., `1.2.3`, `192.168.0.1`)
+//! - Pair literals (e.g., `10x20`, `-5x5`)
+//! - File literals (e.g., `%data/users.csv`)
+//! - URL literals (e.g., `http://host/x`)
+//! - Email literals (e.g., `a@b.com`)
+//! - Issue literals (e.g., `#abc-123`)
+//! - Character literals with caret escapes (e.g., `#"a"`, `#"^/"`, `#"^(41)"`)
 //! - Block structures with nested blocks (e.g., `[outer [inner]]`)
-//! - Path notation (e.g., `word/path/item`)
+//! - Paren groups (e.g., `(1 + 2) * 3`)
+//! - Path notation (e.g., `word/path/item`, `blk/(i + 1)`), with set-paths,
+//!   get-paths and lit-paths (e.g., `obj/field:`, `:obj/field`, `'obj/field`)
 //! - Comments using semicolons (e.g., `; comment`)
 
+use crate::date::{Date, DateError, Time};
+use crate::money::{Money, MoneyError};
+use crate::tuple::{Pair, Tuple, TupleError};
+use std::ops::Range;
 use std::str::CharIndices;
 use thiserror::Error;
 
-/// Errors that can occur during parsing
+/// A position in the parsed input
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Location {
+    /// Line, starting at 1
+    pub line: usize,
+    /// Column in characters, starting at 1
+    pub column: usize,
+    /// Byte offset from the start of the input
+    pub offset: usize,
+}
+
+impl Location {
+    /// Finds the line and column of a byte offset in the input, which must
+    /// be on a character boundary
+    pub fn new(input: &str, offset: usize) -> Self {
+        let before = &input[..offset];
+        let line_start = before.rfind('\n').map_or(0, |at| at + 1);
+        Self {
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+            offset,
+        }
+    }
+}
+
+/// An error that occurred during parsing, and where
+#[derive(Debug, Error)]
+#[error("{kind} at line {}, column {}", location.line, location.column)]
+pub struct ParserError<C> {
+    /// What went wrong
+    pub kind: ParserErrorKind<C>,
+    /// Where it went wrong
+    pub location: Location,
+}
+
+/// Kinds of errors that can occur during parsing
 #[derive(Debug, Error)]
-pub enum ParserError<C> {
+pub enum ParserErrorKind<C> {
     /// Input ended unexpectedly
     #[error("end of input")]
     EndOfInput,
     /// An unexpected character was encountered
     #[error("unexpected character: `{0}`")]
     UnexpectedChar(char),
-    /// Integer value exceeds the range of i32
+    /// Integer value exceeds the range of i64
     #[error("integer overflow")]
     IntegerOverflow,
-    /// Float value exceeds the range of f32
+    /// Float value exceeds the range of f64
     #[error("float overflow")]
     FloatOverflow,
+    /// Binary literal with an invalid digit, or an incomplete byte
+    #[error("invalid binary")]
+    InvalidBinary,
+    /// Money literal is out of range or has too many decimal places
+    #[error("invalid money: {0}")]
+    InvalidMoney(MoneyError),
+    /// Date literal is malformed or out of range
+    #[error("invalid date: {0}")]
+    InvalidDate(DateError),
+    /// Time literal is malformed or out of range
+    #[error("invalid time: {0}")]
+    InvalidTime(DateError),
+    /// Tuple literal is malformed or has items out of range
+    #[error("invalid tuple: {0}")]
+    InvalidTuple(TupleError),
+    /// Pair literal is malformed or out of range
+    #[error("invalid pair: {0}")]
+    InvalidPair(TupleError),
+    /// Email literal without a name or a host, or with several `@`
+    #[error("invalid email")]
+    InvalidEmail,
+    /// Character literal that is empty, has several characters, or an
+    /// invalid code point
+    #[error("invalid character")]
+    InvalidChar,
     /// An unexpected error occurred
     #[error("unexpected error")]
     UnexpectedError,
     /// Attempted to parse an empty word
     #[error("empty word")]
     EmptyWord,
+    /// Block or paren still open at the end of input
+    #[error("unclosed `{0}`")]
+    Unclosed(char),
     /// Error propagated from the collector
     #[error("collector error")]
     CollectorError(#[from] C),
@@ -59,6 +148,21 @@ pub enum WordKind {
     SetWord,
     /// Get-word with leading colon (e.g., `:word`)
     GetWord,
+    /// Lit-word with leading quote (e.g., `'word`)
+    LitWord,
+}
+
+/// Types of paths
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PathKind {
+    /// Regular path (e.g., `word/item`)
+    Path,
+    /// Set-path with trailing colon (e.g., `word/item:`)
+    SetPath,
+    /// Get-path with leading colon (e.g., `:word/item`)
+    GetPath,
+    /// Lit-path with leading quote (e.g., `'word/item`)
+    LitPath,
 }
 
 /// Interface for collecting parsed tokens
@@ -76,10 +180,43 @@ pub trait Collector {
     fn word(&mut self, kind: WordKind, word: &str) -> Result<(), Self::Error>;
 
     /// Called when an integer is parsed
-    fn integer(&mut self, value: i32) -> Result<(), Self::Error>;
+    fn integer(&mut self, value: i64) -> Result<(), Self::Error>;
 
     /// Called when a float is parsed
-    fn float(&mut self, value: f32) -> Result<(), Self::Error>;
+    fn float(&mut self, value: f64) -> Result<(), Self::Error>;
+
+    /// Called when a money amount is parsed
+    fn money(&mut self, value: Money) -> Result<(), Self::Error>;
+
+    /// Called when a binary is parsed
+    fn binary(&mut self, bytes: &[u8]) -> Result<(), Self::Error>;
+
+    /// Called when a date is parsed
+    fn date(&mut self, value: Date) -> Result<(), Self::Error>;
+
+    /// Called when a time is parsed
+    fn time(&mut self, value: Time) -> Result<(), Self::Error>;
+
+    /// Called when a tuple is parsed
+    fn tuple(&mut self, value: Tuple) -> Result<(), Self::Error>;
+
+    /// Called when a pair is parsed
+    fn pair(&mut self, value: Pair) -> Result<(), Self::Error>;
+
+    /// Called when a file is parsed, without its `%`
+    fn file(&mut self, path: &str) -> Result<(), Self::Error>;
+
+    /// Called when a URL is parsed
+    fn url(&mut self, url: &str) -> Result<(), Self::Error>;
+
+    /// Called when an email is parsed
+    fn email(&mut self, email: &str) -> Result<(), Self::Error>;
+
+    /// Called when an issue is parsed, without its `#`
+    fn issue(&mut self, issue: &str) -> Result<(), Self::Error>;
+
+    /// Called when a character is parsed
+    fn char(&mut self, value: char) -> Result<(), Self::Error>;
 
     /// Called at the start of a block
     fn begin_block(&mut self) -> Result<(), Self::Error>;
@@ -90,8 +227,23 @@ pub trait Collector {
     /// Called at the start of a path
     fn begin_path(&mut self) -> Result<(), Self::Error>;
 
-    /// Called at the end of a path
-    fn end_path(&mut self) -> Result<(), Self::Error>;
+    /// Called at the end of a path, with its kind
+    fn end_path(&mut self, kind: PathKind) -> Result<(), Self::Error>;
+
+    /// Called at the start of a paren
+    fn begin_paren(&mut self) -> Result<(), Self::Error>;
+
+    /// Called at the end of a paren
+    fn end_paren(&mut self) -> Result<(), Self::Error>;
+
+    /// Called with the byte range in the input of the value just collected:
+    /// right after a token, and after the end of a block, paren or path.
+    /// Collectors that keep track of where values come from implement it,
+    /// others can ignore it.
+    fn span(&mut self, span: Range<usize>) -> Result<(), Self::Error> {
+        let _ = span;
+        Ok(())
+    }
 }
 
 /// Parser for REBOL-inspired language tokens
@@ -102,7 +254,15 @@ where
     input: &'a str,
     cursor: CharIndices<'a>,
     collector: &'a mut C,
-    in_path: bool,
+    /// Kind of the path being parsed, if any
+    path: Option<PathKind>,
+    /// Where the path being parsed starts
+    path_start: usize,
+    /// For each open paren, the kind and start of the path it is an item
+    /// of, if any
+    parens: Vec<(Option<PathKind>, usize)>,
+    /// The bracket and start of each open block or paren
+    opens: Vec<(char, usize)>,
 }
 
 impl<'a, C> Parser<'a, C>
@@ -115,7 +275,10 @@ where
             input,
             collector,
             cursor: input.char_indices(),
-            in_path: false,
+            path: None,
+            path_start: 0,
+            parens: Vec::new(),
+            opens: Vec::new(),
         }
     }
 
@@ -139,18 +302,34 @@ where
     /// # Example
     ///
     /// ```
-    /// # use rebel::parse::{Collector, WordKind, Parser};
+    /// # use rebel::date::{Date, Time};
+    /// # use rebel::money::Money;
+    /// # use rebel::tuple::{Pair, Tuple};
+    /// # use rebel::parse::{Collector, PathKind, WordKind, Parser};
     /// # struct MyCollector;
     /// # impl Collector for MyCollector {
     /// #     type Error = ();
     /// #     fn string(&mut self, _: &str) -> Result<(), ()> { Ok(()) }
     /// #     fn word(&mut self, _: WordKind, _: &str) -> Result<(), ()> { Ok(()) }
-    /// #     fn integer(&mut self, _: i32) -> Result<(), ()> { Ok(()) }
-    /// #     fn float(&mut self, _: f32) -> Result<(), ()> { Ok(()) }
+    /// #     fn integer(&mut self, _: i64) -> Result<(), ()> { Ok(()) }
+    /// #     fn float(&mut self, _: f64) -> Result<(), ()> { Ok(()) }
+    /// #     fn money(&mut self, _: Money) -> Result<(), ()> { Ok(()) }
+    /// #     fn binary(&mut self, _: &[u8]) -> Result<(), ()> { Ok(()) }
+    /// #     fn date(&mut self, _: Date) -> Result<(), ()> { Ok(()) }
+    /// #     fn time(&mut self, _: Time) -> Result<(), ()> { Ok(()) }
+    /// #     fn tuple(&mut self, _: Tuple) -> Result<(), ()> { Ok(()) }
+    /// #     fn pair(&mut self, _: Pair) -> Result<(), ()> { Ok(()) }
+    /// #     fn file(&mut self, _: &str) -> Result<(), ()> { Ok(()) }
+    /// #     fn url(&mut self, _: &str) -> Result<(), ()> { Ok(()) }
+    /// #     fn email(&mut self, _: &str) -> Result<(), ()> { Ok(()) }
+    /// #     fn issue(&mut self, _: &str) -> Result<(), ()> { Ok(()) }
+    /// #     fn char(&mut self, _: char) -> Result<(), ()> { Ok(()) }
     /// #     fn begin_block(&mut self) -> Result<(), ()> { Ok(()) }
     /// #     fn end_block(&mut self) -> Result<(), ()> { Ok(()) }
     /// #     fn begin_path(&mut self) -> Result<(), ()> { Ok(()) }
-    /// #     fn end_path(&mut self) -> Result<(), ()> { Ok(()) }
+    /// #     fn end_path(&mut self, _: PathKind) -> Result<(), ()> { Ok(()) }
+    /// #     fn begin_paren(&mut self) -> Result<(), ()> { Ok(()) }
+    /// #     fn end_paren(&mut self) -> Result<(), ()> { Ok(()) }
     /// # }
     /// # let mut collector = MyCollector;
     /// // Note: Input content isn't inside brackets, but will be treated as a block
@@ -159,9 +338,11 @@ where
     /// ```
     pub fn parse_block(input: &'a str, collector: &'a mut C) -> Result<(), ParserError<C::Error>> {
         let mut parser = Self::new(input, collector);
-        parser.collector.begin_block()?;
+        let begin = parser.collector.begin_block();
+        begin.map_err(|error| parser.error(error.into(), 0))?;
         parser.do_parse()?;
-        parser.collector.end_block().map_err(Into::into)
+        let end = parser.collector.end_block();
+        end.map_err(|error| parser.error(error.into(), input.len()))
     }
 
     /// Parse input directly with a collector
@@ -183,18 +364,34 @@ where
     /// # Example
     ///
     /// ```
-    /// # use rebel::parse::{Collector, WordKind, Parser};
+    /// # use rebel::date::{Date, Time};
+    /// # use rebel::money::Money;
+    /// # use rebel::tuple::{Pair, Tuple};
+    /// # use rebel::parse::{Collector, PathKind, WordKind, Parser};
     /// # struct MyCollector;
     /// # impl Collector for MyCollector {
     /// #     type Error = ();
     /// #     fn string(&mut self, _: &str) -> Result<(), ()> { Ok(()) }
     /// #     fn word(&mut self, _: WordKind, _: &str) -> Result<(), ()> { Ok(()) }
-    /// #     fn integer(&mut self, _: i32) -> Result<(), ()> { Ok(()) }
-    /// #     fn float(&mut self, _: f32) -> Result<(), ()> { Ok(()) }
+    /// #     fn integer(&mut self, _: i64) -> Result<(), ()> { Ok(()) }
+    /// #     fn float(&mut self, _: f64) -> Result<(), ()> { Ok(()) }
+    /// #     fn money(&mut self, _: Money) -> Result<(), ()> { Ok(()) }
+    /// #     fn binary(&mut self, _: &[u8]) -> Result<(), ()> { Ok(()) }
+    /// #     fn date(&mut self, _: Date) -> Result<(), ()> { Ok(()) }
+    /// #     fn time(&mut self, _: Time) -> Result<(), ()> { Ok(()) }
+    /// #     fn tuple(&mut self, _: Tuple) -> Result<(), ()> { Ok(()) }
+    /// #     fn pair(&mut self, _: Pair) -> Result<(), ()> { Ok(()) }
+    /// #     fn file(&mut self, _: &str) -> Result<(), ()> { Ok(()) }
+    /// #     fn url(&mut self, _: &str) -> Result<(), ()> { Ok(()) }
+    /// #     fn email(&mut self, _: &str) -> Result<(), ()> { Ok(()) }
+    /// #     fn issue(&mut self, _: &str) -> Result<(), ()> { Ok(()) }
+    /// #     fn char(&mut self, _: char) -> Result<(), ()> { Ok(()) }
     /// #     fn begin_block(&mut self) -> Result<(), ()> { Ok(()) }
     /// #     fn end_block(&mut self) -> Result<(), ()> { Ok(()) }
     /// #     fn begin_path(&mut self) -> Result<(), ()> { Ok(()) }
-    /// #     fn end_path(&mut self) -> Result<(), ()> { Ok(()) }
+    /// #     fn end_path(&mut self, _: PathKind) -> Result<(), ()> { Ok(()) }
+    /// #     fn begin_paren(&mut self) -> Result<(), ()> { Ok(()) }
+    /// #     fn end_paren(&mut self) -> Result<(), ()> { Ok(()) }
     /// # }
     /// # let mut collector = MyCollector;
     /// let input = "[word 123 \"string\"]";
@@ -205,6 +402,86 @@ where
         parser.do_parse()
     }
 
+    /// Parse input directly with a collector, recovering from errors
+    ///
+    /// Like `parse`, but after a syntax error skips the rest of the bad
+    /// token, up to the next whitespace or bracket, and carries on, so the
+    /// collector still receives every token that could be parsed. A stray
+    /// `]` or `)` is reported and skipped, and each block or paren still
+    /// open at the end is reported and closed. Errors from the collector
+    /// stop parsing.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` if parsing succeeded
+    /// * `Err(errors)` with every error, in the order they occurred
+    pub fn parse_recovering(
+        input: &'a str,
+        collector: &'a mut C,
+    ) -> Result<(), Vec<ParserError<C::Error>>> {
+        let mut parser = Self::new(input, collector);
+        let errors = parser.do_parse_recovering();
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Parse input as a block, recovering from errors
+    ///
+    /// Wraps the input in a block like `parse_block`, and recovers from
+    /// errors like `parse_recovering`.
+    pub fn parse_block_recovering(
+        input: &'a str,
+        collector: &'a mut C,
+    ) -> Result<(), Vec<ParserError<C::Error>>> {
+        let mut parser = Self::new(input, collector);
+        let begin = parser.collector.begin_block();
+        begin.map_err(|error| vec![parser.error(error.into(), 0)])?;
+        let mut errors = parser.do_parse_recovering();
+        let fatal = matches!(
+            errors.last(),
+            Some(ParserError {
+                kind: ParserErrorKind::CollectorError(_),
+                ..
+            })
+        );
+        if !fatal {
+            let end = parser.collector.end_block();
+            if let Err(error) = end {
+                errors.push(parser.error(error.into(), input.len()));
+            }
+        }
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Locates an error in the token starting at `start`: the end of input,
+    /// the unexpected character, or else the start of the token
+    fn error(&self, kind: ParserErrorKind<C::Error>, start: usize) -> ParserError<C::Error> {
+        let offset = match kind {
+            ParserErrorKind::EndOfInput => self.input.len(),
+            ParserErrorKind::UnexpectedChar(char) => {
+                // the character may have been peeked rather than consumed
+                let end = match self.cursor.clone().next() {
+                    Some((pos, next)) if next == char => pos + char.len_utf8(),
+                    _ => self.cursor.offset(),
+                };
+                let token = self.input.get(start..end).unwrap_or_default();
+                token.rfind(char).map_or(start, |at| start + at)
+            }
+            _ => start,
+        };
+        ParserError {
+            kind,
+            location: Location::new(self.input, offset),
+        }
+    }
+
     fn skip_whitespace(&mut self) -> Option<(usize, char)> {
         while let Some((pos, char)) = self.cursor.next() {
             if char.is_ascii_whitespace() {
@@ -224,7 +501,7 @@ where
         None
     }
 
-    fn parse_string(&mut self, pos: usize) -> Result<Option<char>, ParserError<C::Error>> {
+    fn parse_string(&mut self, pos: usize) -> Result<Option<char>, ParserErrorKind<C::Error>> {
         let _start_pos = pos + 1; // Skip the opening quote
         let mut result = String::new();
         let mut escaped = false;
@@ -238,7 +515,7 @@ where
                     't' => '\t',
                     '"' => '"',
                     '\\' => '\\',
-                    _ => return Err(ParserError::UnexpectedChar(char)),
+                    _ => return Err(ParserErrorKind::UnexpectedChar(char)),
                 };
                 result.push(escaped_char);
                 escaped = false;
@@ -253,7 +530,214 @@ where
         }
 
         // If we get here, we never found the closing quote
-        Err(ParserError::EndOfInput)
+        Err(ParserErrorKind::EndOfInput)
+    }
+
+    /// Parses a brace string after its `{`, up to the matching `}`. Nested
+    /// braces must balance, unless escaped as `^{` or `^}`.
+    fn parse_brace_string(&mut self) -> Result<Option<char>, ParserErrorKind<C::Error>> {
+        let mut result = String::new();
+        let mut depth = 0usize;
+        while let Some((_, char)) = self.cursor.next() {
+            match char {
+                '^' => result.push(self.parse_caret_escape()?),
+                '}' if depth == 0 => {
+                    return Ok(self.collector.string(&result).map(|_| None)?);
+                }
+                '{' => {
+                    depth += 1;
+                    result.push(char);
+                }
+                '}' => {
+                    depth -= 1;
+                    result.push(char);
+                }
+                char => result.push(char),
+            }
+        }
+        Err(ParserErrorKind::EndOfInput)
+    }
+
+    /// Parses a character literal after its `#"`, with caret escapes:
+    /// `^/` newline, `^-` tab, `^@` null, `^A` to `^Z` control characters,
+    /// `^(41)` a hex code point, and `^^`, `^"`, `^{`, `^}` themselves.
+    fn parse_char(&mut self) -> Result<Option<char>, ParserErrorKind<C::Error>> {
+        let mut chars = Vec::new();
+        loop {
+            match self.cursor.next() {
+                Some((_, '"')) => break,
+                Some((_, '^')) => chars.push(self.parse_caret_escape()?),
+                Some((_, char)) => chars.push(char),
+                None => return Err(ParserErrorKind::EndOfInput),
+            }
+        }
+        match chars[..] {
+            [char] => Ok(self.collector.char(char).map(|_| None)?),
+            _ => Err(ParserErrorKind::InvalidChar),
+        }
+    }
+
+    fn parse_caret_escape(&mut self) -> Result<char, ParserErrorKind<C::Error>> {
+        match self.cursor.next() {
+            Some((_, '/')) => Ok('\n'),
+            Some((_, '-')) => Ok('\t'),
+            Some((_, '@')) => Ok('\0'),
+            Some((_, char @ ('^' | '"' | '{' | '}'))) => Ok(char),
+            Some((_, char)) if char.is_ascii_alphabetic() => {
+                Ok((char.to_ascii_uppercase() as u8 - b'@') as char)
+            }
+            Some((_, '(')) => {
+                let mut code = String::new();
+                for (_, char) in self.cursor.by_ref() {
+                    if char == ')' {
+                        return u32::from_str_radix(&code, 16)
+                            .ok()
+                            .and_then(char::from_u32)
+                            .ok_or(ParserErrorKind::InvalidChar);
+                    }
+                    code.push(char);
+                }
+                Err(ParserErrorKind::EndOfInput)
+            }
+            Some((_, char)) => Err(ParserErrorKind::UnexpectedChar(char)),
+            None => Err(ParserErrorKind::EndOfInput),
+        }
+    }
+
+    /// Parses a binary literal from its opening brace: hex digits for base
+    /// 16, base64 for base 64, whitespace ignored.
+    fn parse_binary(&mut self, base: u32) -> Result<Option<char>, ParserErrorKind<C::Error>> {
+        match self.cursor.next() {
+            Some((_, '{')) => {}
+            Some((_, char)) => return Err(ParserErrorKind::UnexpectedChar(char)),
+            None => return Err(ParserErrorKind::EndOfInput),
+        }
+        let mut digits = Vec::new();
+        for (_, char) in self.cursor.by_ref() {
+            match char {
+                '}' => {
+                    let bytes = match base {
+                        16 => decode_hex(&digits),
+                        _ => decode_base64(&digits),
+                    }
+                    .ok_or(ParserErrorKind::InvalidBinary)?;
+                    return Ok(self.collector.binary(&bytes).map(|_| None)?);
+                }
+                c if c.is_ascii_whitespace() => {}
+                c if c.is_ascii() => digits.push(c as u8),
+                c => return Err(ParserErrorKind::UnexpectedChar(c)),
+            }
+        }
+        Err(ParserErrorKind::EndOfInput)
+    }
+
+    /// Reads the rest of a literal, up to whitespace or the end of a block.
+    /// Returns the literal from `start` and the `]` that ended it, if any.
+    fn read_literal(
+        &mut self,
+        start: usize,
+    ) -> Result<(&'a str, Option<char>), ParserErrorKind<C::Error>> {
+        let mut end = self.input.len();
+        let mut consumed = None;
+        for (pos, char) in self.cursor.by_ref() {
+            if matches!(char, ']' | ')') || char.is_ascii_whitespace() {
+                end = pos;
+                consumed = matches!(char, ']' | ')').then_some(char);
+                break;
+            }
+        }
+        let literal = self
+            .input
+            .get(start..end)
+            .ok_or(ParserErrorKind::UnexpectedError)?;
+        Ok((literal, consumed))
+    }
+
+    /// Parses a date or a time literal from its start
+    fn parse_temporal(
+        &mut self,
+        start: usize,
+        is_date: bool,
+    ) -> Result<Option<char>, ParserErrorKind<C::Error>> {
+        let (literal, consumed) = self.read_literal(start)?;
+        if is_date {
+            let date = literal
+                .parse::<Date>()
+                .map_err(ParserErrorKind::InvalidDate)?;
+            Ok(self.collector.date(date).map(|_| consumed)?)
+        } else {
+            let time = literal
+                .parse::<Time>()
+                .map_err(ParserErrorKind::InvalidTime)?;
+            Ok(self.collector.time(time).map(|_| consumed)?)
+        }
+    }
+
+    /// Parses a tuple literal from its start
+    fn parse_tuple(&mut self, start: usize) -> Result<Option<char>, ParserErrorKind<C::Error>> {
+        let (literal, consumed) = self.read_literal(start)?;
+        let tuple = literal
+            .parse::<Tuple>()
+            .map_err(ParserErrorKind::InvalidTuple)?;
+        Ok(self.collector.tuple(tuple).map(|_| consumed)?)
+    }
+
+    /// Parses a pair literal from its start
+    fn parse_pair(&mut self, start: usize) -> Result<Option<char>, ParserErrorKind<C::Error>> {
+        let (literal, consumed) = self.read_literal(start)?;
+        let pair = literal
+            .parse::<Pair>()
+            .map_err(ParserErrorKind::InvalidPair)?;
+        Ok(self.collector.pair(pair).map(|_| consumed)?)
+    }
+
+    /// Parses a file from its `%`, or an issue from its `#`
+    fn parse_file_or_issue(
+        &mut self,
+        start: usize,
+        sigil: char,
+    ) -> Result<Option<char>, ParserErrorKind<C::Error>> {
+        let (literal, consumed) = self.read_literal(start)?;
+        let text = &literal[1..];
+        if text.is_empty() {
+            return Err(ParserErrorKind::UnexpectedChar(sigil));
+        }
+        if sigil == '%' {
+            Ok(self.collector.file(text).map(|_| consumed)?)
+        } else {
+            Ok(self.collector.issue(text).map(|_| consumed)?)
+        }
+    }
+
+    /// Parses a URL from its start, after reading its scheme
+    fn parse_url(&mut self, start: usize) -> Result<Option<char>, ParserErrorKind<C::Error>> {
+        let (literal, consumed) = self.read_literal(start)?;
+        match literal.split_once("://") {
+            Some((_, rest)) if !rest.is_empty() => {
+                Ok(self.collector.url(literal).map(|_| consumed)?)
+            }
+            _ => Err(ParserErrorKind::UnexpectedChar(':')),
+        }
+    }
+
+    /// Parses an email from its start, after reading part of its name
+    fn parse_email(&mut self, start: usize) -> Result<Option<char>, ParserErrorKind<C::Error>> {
+        let (literal, consumed) = self.read_literal(start)?;
+        match literal.split_once('@') {
+            Some((name, host)) if !name.is_empty() && !host.is_empty() && !host.contains('@') => {
+                Ok(self.collector.email(literal).map(|_| consumed)?)
+            }
+            _ => Err(ParserErrorKind::InvalidEmail),
+        }
+    }
+
+    /// Returns true if the literal being read has an `@` before its end
+    fn is_email_ahead(&self, pos: usize) -> bool {
+        let rest = &self.input[pos..];
+        let end = rest
+            .find(|c: char| matches!(c, ']' | ')') || c.is_ascii_whitespace())
+            .unwrap_or(rest.len());
+        rest[..end].contains('@')
     }
 
     fn collect_word(
@@ -261,29 +745,69 @@ where
         symbol: &str,
         kind: WordKind,
         consumed: Option<char>,
-    ) -> Result<Option<char>, ParserError<C::Error>> {
-        if let Some('/') = consumed {
-            if !self.in_path {
-                self.in_path = true;
-                self.collector.begin_path()?
+    ) -> Result<Option<char>, ParserErrorKind<C::Error>> {
+        // the first word of a path gives the kind of the path, and a set-word
+        // ends a path as a set-path; the words themselves are plain
+        let kind = match (self.path, consumed, kind) {
+            (None, Some('/'), kind) => {
+                self.path = Some(match kind {
+                    WordKind::GetWord => PathKind::GetPath,
+                    WordKind::LitWord => PathKind::LitPath,
+                    _ => PathKind::Path,
+                });
+                self.collector.begin_path()?;
+                WordKind::Word
             }
-        }
+            (Some(_), _, WordKind::SetWord) => {
+                self.set_path()?;
+                WordKind::Word
+            }
+            (_, _, kind) => kind,
+        };
         Ok(self.collector.word(kind, symbol).map(|_| consumed)?)
     }
 
-    fn parse_word(&mut self, start_pos: usize) -> Result<Option<char>, ParserError<C::Error>> {
+    /// Turns the path being parsed into a set-path, which only a regular
+    /// path can be
+    fn set_path(&mut self) -> Result<(), ParserErrorKind<C::Error>> {
+        if self.path != Some(PathKind::Path) {
+            return Err(ParserErrorKind::UnexpectedChar(':'));
+        }
+        self.path = Some(PathKind::SetPath);
+        Ok(())
+    }
+
+    fn parse_word(&mut self, start_pos: usize) -> Result<Option<char>, ParserErrorKind<C::Error>> {
         let mut kind = WordKind::Word;
         let mut word_start = start_pos;
 
-        // Special handling for get-words starting with a colon
-        if self.input.as_bytes().get(start_pos) == Some(&b':') {
-            kind = WordKind::GetWord;
-            word_start = start_pos + 1; // Skip the colon for get-words
+        // Special handling for get-words starting with a colon, and
+        // lit-words starting with a quote
+        match self.input.as_bytes().get(start_pos) {
+            Some(b':') => {
+                kind = WordKind::GetWord;
+                word_start = start_pos + 1; // Skip the colon for get-words
+            }
+            Some(b'\'') => {
+                kind = WordKind::LitWord;
+                word_start = start_pos + 1;
+            }
+            _ => {}
         }
 
         let consumed = loop {
             match self.cursor.next() {
                 Some((pos, char)) => match char {
+                    ':' if kind == WordKind::Word && self.input[pos..].starts_with("://") => {
+                        return self.parse_url(start_pos);
+                    }
+                    '@' if kind == WordKind::Word => return self.parse_email(start_pos),
+                    '.' if kind == WordKind::Word && self.is_email_ahead(pos) => {
+                        return self.parse_email(start_pos);
+                    }
+                    ':' if kind != WordKind::Word => {
+                        return Err(ParserErrorKind::UnexpectedChar(char));
+                    }
                     ':' => {
                         if pos != start_pos {
                             // Not at the beginning (already handled)
@@ -291,10 +815,11 @@ where
                             break Some(char);
                         }
                     }
-                    ']' | '/' => break Some(char),
-                    c if c.is_ascii_alphanumeric() || c == '_' || c == '-' || c == '?' => {}
+                    ']' | ')' | '/' => break Some(char),
+                    c if c.is_ascii_alphanumeric()
+                        || matches!(c, '_' | '-' | '?' | '<' | '>' | '=' | '*') => {}
                     c if c.is_ascii_whitespace() => break Some(char),
-                    _ => return Err(ParserError::UnexpectedChar(char)),
+                    _ => return Err(ParserErrorKind::UnexpectedChar(char)),
                 },
                 None => break None,
             }
@@ -302,23 +827,28 @@ where
 
         let pos = self.cursor.offset() - if consumed.is_some() { 1 } else { 0 };
         if pos <= word_start {
-            return Err(ParserError::EmptyWord);
+            return Err(ParserErrorKind::EmptyWord);
         }
         let symbol = self
             .input
             .get(word_start..pos)
-            .ok_or(ParserError::UnexpectedError)?;
+            .ok_or(ParserErrorKind::UnexpectedError)?;
 
         self.collect_word(symbol, kind, consumed)
     }
 
-    fn parse_number(&mut self, char: char) -> Result<Option<char>, ParserError<C::Error>> {
-        let mut int_value: i32 = 0;
-        let mut float_value: f32 = 0.0;
+    fn parse_number(
+        &mut self,
+        start: usize,
+        char: char,
+    ) -> Result<Option<char>, ParserErrorKind<C::Error>> {
+        // None once the integer part overflows, an error unless a float follows
+        let mut int_value = Some(0i64);
         let mut is_negative = false;
         let mut has_digits = false;
         let mut is_float = false;
-        let mut decimal_position = 0.1;
+        let mut is_money = char == '$';
+        let mut end = start + 1;
         let mut consumed = None;
 
         match char {
@@ -326,105 +856,431 @@ where
             '-' => {
                 is_negative = true;
             }
+            '$' => {}
             c if c.is_ascii_digit() => {
-                int_value = c.to_digit(10).ok_or(ParserError::UnexpectedError)? as i32;
+                int_value = Some(c.to_digit(10).ok_or(ParserErrorKind::UnexpectedError)? as i64);
                 has_digits = true;
             }
-            _ => return Err(ParserError::UnexpectedChar(char)),
+            _ => return Err(ParserErrorKind::UnexpectedChar(char)),
         }
 
-        for (_, char) in self.cursor.by_ref() {
+        for (pos, char) in self.cursor.by_ref() {
             match char {
+                '$' if !is_money && !has_digits && pos == start + 1 => {
+                    is_money = true;
+                    end = pos + 1;
+                }
                 '.' if !is_float => {
                     is_float = true;
-                    float_value = int_value as f32;
+                    end = pos + 1;
+                }
+                '#' if matches!(self.input.get(start..end), Some("16" | "64")) => {
+                    let base = if &self.input[start..end] == "16" {
+                        16
+                    } else {
+                        64
+                    };
+                    return self.parse_binary(base);
+                }
+                ':' if self.path.is_some()
+                    && has_digits
+                    && !is_float
+                    && !is_money
+                    && !self.input[pos + 1..].starts_with(|c: char| c.is_ascii_digit()) =>
+                {
+                    // an index ending a set-path, like `blk/1:`
+                    self.set_path()?;
+                    consumed = Some(char);
+                    break;
+                }
+                ':' | '-' if has_digits && !is_float && !is_money => {
+                    return self.parse_temporal(start, char == '-');
+                }
+                '.' if is_float && !is_money => return self.parse_tuple(start),
+                'x' | 'X' if has_digits && !is_float && !is_money => {
+                    return self.parse_pair(start);
                 }
                 c if c.is_ascii_digit() => {
                     has_digits = true;
-                    let digit = c.to_digit(10).ok_or(ParserError::UnexpectedError)? as i32;
-
-                    if is_float {
-                        let digit_float = digit as f32 * decimal_position;
-                        float_value += digit_float;
-                        decimal_position *= 0.1;
-                    } else {
-                        // Still parsing integer part
-                        int_value = int_value
-                            .checked_mul(10)
-                            .and_then(|v| v.checked_add(digit))
-                            .ok_or(ParserError::IntegerOverflow)?;
+                    end = pos + 1;
+                    if !is_float {
+                        let digit = c.to_digit(10).ok_or(ParserErrorKind::UnexpectedError)? as i64;
+                        // negative numbers accumulate downwards, so i64::MIN parses
+                        int_value = int_value.and_then(|v| v.checked_mul(10)).and_then(|v| {
+                            if is_negative {
+                                v.checked_sub(digit)
+                            } else {
+                                v.checked_add(digit)
+                            }
+                        });
                     }
                 }
-                ']' => {
+                ']' | ')' => {
+                    consumed = Some(char);
+                    break;
+                }
+                '/' if self.path.is_some() => {
                     consumed = Some(char);
                     break;
                 }
                 c if c.is_ascii_whitespace() => {
                     break;
                 }
+                _ if has_digits && !is_money && self.is_email_ahead(pos) => {
+                    return self.parse_email(start);
+                }
                 _ => {
-                    return Err(ParserError::UnexpectedChar(char));
+                    return Err(ParserErrorKind::UnexpectedChar(char));
                 }
             }
         }
 
-        if !has_digits {
+        if is_money {
+            let money = self
+                .input
+                .get(start..end)
+                .ok_or(ParserErrorKind::UnexpectedError)?
+                .parse::<Money>()
+                .map_err(|error| match error {
+                    MoneyError::Invalid => ParserErrorKind::UnexpectedChar('$'),
+                    error => ParserErrorKind::InvalidMoney(error),
+                })?;
+            self.collector
+                .money(money)
+                .map(|_| consumed)
+                .map_err(Into::into)
+        } else if !has_digits {
             self.collector
                 .word(WordKind::Word, if is_negative { "-" } else { "+" })
                 .map(|_| consumed)
                 .map_err(Into::into)
         } else if is_float {
-            if is_negative {
-                float_value = -float_value;
+            // the literal is parsed as a whole, so it rounds to the nearest f64
+            let float_value = self
+                .input
+                .get(start..end)
+                .and_then(|literal| literal.parse::<f64>().ok())
+                .ok_or(ParserErrorKind::UnexpectedError)?;
+            if float_value.is_infinite() {
+                return Err(ParserErrorKind::FloatOverflow);
             }
             self.collector
                 .float(float_value)
                 .map(|_| consumed)
                 .map_err(Into::into)
         } else {
-            if is_negative {
-                int_value = int_value
-                    .checked_neg()
-                    .ok_or(ParserError::IntegerOverflow)?;
-            }
             self.collector
-                .integer(int_value)
+                .integer(int_value.ok_or(ParserErrorKind::IntegerOverflow)?)
                 .map(|_| consumed)
                 .map_err(Into::into)
         }
     }
 
-    fn process_block_end(&mut self, consumed: Option<char>) -> Result<(), ParserError<C::Error>> {
+    /// Ends the path, block or paren the token just parsed closes, if any.
+    /// `end` is where the token ends, without the character it consumed.
+    fn process_block_end(
+        &mut self,
+        consumed: Option<char>,
+        end: usize,
+    ) -> Result<(), ParserErrorKind<C::Error>> {
         match consumed {
             Some('/') => {}
             _ => {
-                if self.in_path {
-                    self.in_path = false;
-                    self.collector.end_path()?;
+                if let Some(path) = self.path.take() {
+                    self.collector.end_path(path)?;
+                    self.collector.span(self.path_start..end)?;
                 }
             }
         }
-        if let Some(']') = consumed {
-            self.collector.end_block()?;
+        match consumed {
+            Some(']') => {
+                let start = self.close('[', ']')?;
+                self.collector.end_block()?;
+                self.collector.span(start..self.cursor.offset())?;
+            }
+            Some(')') => {
+                let start = self.close('(', ')')?;
+                self.collector.end_paren()?;
+                self.collector.span(start..self.cursor.offset())?;
+                // a paren in a path is one of its items, and may be followed
+                // by more, or end a set-path
+                if let Some((Some(path), path_start)) = self.parens.pop() {
+                    self.path_start = path_start;
+                    match self.cursor.clone().next() {
+                        Some((_, '/')) => {
+                            self.cursor.next();
+                            self.path = Some(path);
+                        }
+                        Some((_, ':')) if path == PathKind::Path => {
+                            self.cursor.next();
+                            self.collector.end_path(PathKind::SetPath)?;
+                            self.collector.span(path_start..self.cursor.offset())?;
+                        }
+                        Some((_, ':')) => return Err(ParserErrorKind::UnexpectedChar(':')),
+                        _ => {
+                            self.collector.end_path(path)?;
+                            self.collector.span(path_start..self.cursor.offset())?;
+                        }
+                    }
+                }
+            }
+            _ => {}
         }
         Ok(())
     }
 
+    /// Returns the start of the innermost open block or paren, which
+    /// `close` must end
+    fn close(&mut self, open: char, close: char) -> Result<usize, ParserErrorKind<C::Error>> {
+        match self.opens.last() {
+            Some(&(bracket, start)) if bracket == open => {
+                self.opens.pop();
+                Ok(start)
+            }
+            _ => Err(ParserErrorKind::UnexpectedChar(close)),
+        }
+    }
+
     fn do_parse(&mut self) -> Result<(), ParserError<C::Error>> {
         while let Some((pos, char)) = self.skip_whitespace() {
-            let consumed = match char {
-                '[' => self.collector.begin_block().map(|()| None)?,
-                ']' => Some(char),
-                '"' => self.parse_string(pos)?,
-                ':' => self.parse_word(pos)?, // Special handling for get-words
-                c if c.is_ascii_alphabetic() || c == '<' || c == '>' => self.parse_word(pos)?,
-                c if c.is_ascii_digit() || c == '+' || c == '-' => self.parse_number(c)?,
-                _ => return Err(ParserError::UnexpectedChar(char)),
+            self.parse_token(pos, char)
+                .map_err(|kind| self.error(kind, pos))?;
+        }
+        match self.opens.first() {
+            Some(&(open, start)) => Err(self.error(ParserErrorKind::Unclosed(open), start)),
+            None => Ok(()),
+        }
+    }
+
+    /// Parses to the end of input, returning every error. Only an error
+    /// from the collector stops parsing, as the last error. Blocks and
+    /// parens left open at the end are reported and closed.
+    fn do_parse_recovering(&mut self) -> Vec<ParserError<C::Error>> {
+        let mut errors = Vec::new();
+        while let Some((pos, char)) = self.skip_whitespace() {
+            let token = self.cursor.clone();
+            let Err(kind) = self.parse_token(pos, char) else {
+                continue;
+            };
+            let fatal = matches!(kind, ParserErrorKind::CollectorError(_));
+            let at_end = matches!(kind, ParserErrorKind::EndOfInput);
+            let is_string = self.is_string_token(pos);
+            // a token may end with a bracket that was never opened, once the
+            // token itself is collected
+            let stray = match kind {
+                ParserErrorKind::UnexpectedChar(close @ (']' | ')')) => {
+                    !is_string && self.input[pos..self.cursor.offset()].ends_with(close)
+                }
+                _ => false,
             };
-            self.process_block_end(consumed)?;
+            errors.push(self.error(kind, pos));
+            if fatal {
+                return errors;
+            }
+            if stray {
+                continue;
+            }
+            // a bad escape or character may come before the end of a string,
+            // which is skipped as a whole so a bracket in it closes nothing
+            if is_string && !at_end {
+                self.cursor = token;
+                self.skip_string(char);
+            }
+            let in_token = is_string || at_end;
+            if let Err(kind) = self.recover(pos, in_token) {
+                let fatal = matches!(kind, ParserErrorKind::CollectorError(_));
+                errors.push(self.error(kind, pos));
+                if fatal {
+                    return errors;
+                }
+            }
+        }
+        for &(open, start) in &self.opens {
+            errors.push(self.error(ParserErrorKind::Unclosed(open), start));
+        }
+        while let Some((open, _)) = self.opens.pop() {
+            if let Err(kind) = self.end_unclosed(open) {
+                errors.push(self.error(kind, self.input.len()));
+                break;
+            }
+        }
+        errors
+    }
+
+    /// Returns true if the token at `start` is a string, a character or a
+    /// binary, which may contain brackets
+    fn is_string_token(&self, start: usize) -> bool {
+        let token = &self.input[start..];
+        token.starts_with(['"', '{']) || token.starts_with("#\"") || token.starts_with("#{")
+    }
+
+    /// Skips the rest of the string, character or binary starting with
+    /// `first`, up to its closing delimiter, without checking its escapes
+    fn skip_string(&mut self, first: char) {
+        let (escape, close) = match first {
+            '"' => ('\\', '"'),
+            '{' => ('^', '}'),
+            _ => match self.cursor.next() {
+                Some((_, '"')) => ('^', '"'),
+                _ => ('^', '}'),
+            },
+        };
+        let mut depth = 0usize;
+        while let Some((_, char)) = self.cursor.next() {
+            match char {
+                _ if char == escape => {
+                    self.cursor.next();
+                }
+                '{' if close == '}' => depth += 1,
+                '}' if close == '}' && depth > 0 => depth -= 1,
+                _ if char == close => return,
+                _ => {}
+            }
+        }
+    }
+
+    /// Ends a block or paren left open at the end of input, and the path
+    /// a paren was an item of
+    fn end_unclosed(&mut self, open: char) -> Result<(), ParserErrorKind<C::Error>> {
+        if open == '[' {
+            return Ok(self.collector.end_block()?);
+        }
+        self.collector.end_paren()?;
+        if let Some((Some(path), _)) = self.parens.pop() {
+            self.collector.end_path(path)?;
         }
         Ok(())
     }
+
+    /// Skips the rest of a bad token starting at `start`, up to whitespace
+    /// or a bracket, and ends the path it was in. Literals are read up to
+    /// the whitespace or bracket ending them before they are checked, so
+    /// whitespace the bad token consumed ends it, and a bracket still closes
+    /// its block or paren, unless it is `in_token`.
+    fn recover(&mut self, start: usize, in_token: bool) -> Result<(), ParserErrorKind<C::Error>> {
+        let offset = self.cursor.offset();
+        let consumed = match self.input[start..offset].chars().next_back() {
+            Some(char @ (']' | ')')) if offset - 1 > start && !in_token => Some(char),
+            // the whitespace that ended the token
+            Some(char) if char.is_ascii_whitespace() => None,
+            _ => {
+                while let Some((_, char)) = self.cursor.clone().next() {
+                    if char.is_ascii_whitespace() || matches!(char, '[' | ']' | '(' | ')') {
+                        break;
+                    }
+                    self.cursor.next();
+                }
+                None
+            }
+        };
+        let end = self.cursor.offset() - consumed.map_or(0, char::len_utf8);
+        self.process_block_end(consumed, end)
+    }
+
+    fn parse_token(&mut self, pos: usize, char: char) -> Result<(), ParserErrorKind<C::Error>> {
+        let in_path = self.path.is_some();
+        let consumed = match char {
+            '[' => {
+                self.opens.push((char, pos));
+                return Ok(self.collector.begin_block()?);
+            }
+            '(' => {
+                self.opens.push((char, pos));
+                self.parens.push((self.path.take(), self.path_start));
+                return Ok(self.collector.begin_paren()?);
+            }
+            ']' | ')' => return self.process_block_end(Some(char), pos),
+            '"' => self.parse_string(pos)?,
+            '{' => self.parse_brace_string()?,
+            '#' => match self.cursor.clone().next() {
+                Some((_, '{')) => self.parse_binary(16)?,
+                Some((_, '"')) => {
+                    self.cursor.next();
+                    self.parse_char()?
+                }
+                _ => self.parse_file_or_issue(pos, char)?,
+            },
+            '%' => self.parse_file_or_issue(pos, char)?,
+            ':' | '\'' => self.parse_word(pos)?, // Special handling for get-words and lit-words
+            c if c.is_ascii_alphabetic() || matches!(c, '<' | '>' | '=' | '*') => {
+                self.parse_word(pos)?
+            }
+            c if c.is_ascii_digit() || matches!(c, '+' | '-' | '$') => {
+                self.parse_number(pos, char)?
+            }
+            _ => return Err(ParserErrorKind::UnexpectedChar(char)),
+        };
+
+        // the token ends before the bracket or slash it consumed, and before
+        // any whitespace
+        let mut end = self.cursor.offset();
+        if let Some(']' | ')' | '/') = consumed {
+            end -= 1;
+        }
+        end = pos + self.input[pos..end].trim_end().len();
+        // the first word of a path is spanned without its prefix, and the
+        // last item of a set-path without its colon
+        let mut start = pos;
+        if !in_path && self.path.is_some() {
+            self.path_start = pos;
+            if matches!(char, ':' | '\'') {
+                start += 1;
+            }
+        }
+        let item_end = match consumed {
+            Some(':') if self.path == Some(PathKind::SetPath) => end - 1,
+            _ => end,
+        };
+        self.collector.span(start..item_end)?;
+        self.process_block_end(consumed, end)
+    }
+}
+
+/// Decodes pairs of hex digits
+fn decode_hex(digits: &[u8]) -> Option<Vec<u8>> {
+    if !digits.len().is_multiple_of(2) {
+        return None;
+    }
+    digits
+        .chunks(2)
+        .map(|pair| {
+            let high = (pair[0] as char).to_digit(16)?;
+            let low = (pair[1] as char).to_digit(16)?;
+            Some((high << 4 | low) as u8)
+        })
+        .collect()
+}
+
+/// Decodes standard base64, with or without its `=` padding
+fn decode_base64(digits: &[u8]) -> Option<Vec<u8>> {
+    let data = digits
+        .strip_suffix(b"==")
+        .or_else(|| digits.strip_suffix(b"="))
+        .unwrap_or(digits);
+    if (data.len() != digits.len() && !digits.len().is_multiple_of(4)) || data.len() % 4 == 1 {
+        return None;
+    }
+    let mut bytes = Vec::with_capacity(data.len() * 3 / 4);
+    let (mut bits, mut count) = (0u32, 0);
+    for &digit in data {
+        let value = match digit {
+            b'A'..=b'Z' => digit - b'A',
+            b'a'..=b'z' => digit - b'a' + 26,
+            b'0'..=b'9' => digit - b'0' + 52,
+            b'+' => 62,
+            b'/' => 63,
+            _ => return None,
+        };
+        bits = bits << 6 | value as u32;
+        count += 6;
+        if count >= 8 {
+            count -= 8;
+            bytes.push((bits >> count) as u8);
+            bits &= (1 << count) - 1;
+        }
+    }
+    // the bits past the last byte must be zero
+    (bits == 0).then_some(bytes)
 }
 
 #[cfg(test)]
@@ -437,6 +1293,8 @@ mod tests {
     pub struct SimpleCollector {
         /// Collected tokens as formatted strings
         pub tokens: Vec<String>,
+        /// Spans of the collected values, in the order they end
+        pub spans: Vec<Range<usize>>,
     }
 
     impl Collector for SimpleCollector {
@@ -452,22 +1310,78 @@ mod tests {
                 WordKind::Word => "Word",
                 WordKind::SetWord => "SetWord",
                 WordKind::GetWord => "GetWord",
+                WordKind::LitWord => "LitWord",
             };
             self.tokens.push(format!("{}: {}", kind_str, word));
             Ok(())
         }
 
-        fn integer(&mut self, value: i32) -> Result<(), Self::Error> {
+        fn integer(&mut self, value: i64) -> Result<(), Self::Error> {
             self.tokens.push(format!("Integer: {}", value));
             Ok(())
         }
 
-        fn float(&mut self, value: f32) -> Result<(), Self::Error> {
+        fn float(&mut self, value: f64) -> Result<(), Self::Error> {
             // Format with enough precision to distinguish common values like PI
             self.tokens.push(format!("Float: {:.6}", value));
             Ok(())
         }
 
+        fn money(&mut self, value: Money) -> Result<(), Self::Error> {
+            self.tokens.push(format!("Money: {value}"));
+            Ok(())
+        }
+
+        fn binary(&mut self, bytes: &[u8]) -> Result<(), Self::Error> {
+            self.tokens.push(format!("Binary: {bytes:02X?}"));
+            Ok(())
+        }
+
+        fn date(&mut self, value: Date) -> Result<(), Self::Error> {
+            self.tokens.push(format!("Date: {value}"));
+            Ok(())
+        }
+
+        fn time(&mut self, value: Time) -> Result<(), Self::Error> {
+            self.tokens.push(format!("Time: {value}"));
+            Ok(())
+        }
+
+        fn tuple(&mut self, value: Tuple) -> Result<(), Self::Error> {
+            self.tokens.push(format!("Tuple: {value}"));
+            Ok(())
+        }
+
+        fn pair(&mut self, value: Pair) -> Result<(), Self::Error> {
+            self.tokens.push(format!("Pair: {value}"));
+            Ok(())
+        }
+
+        fn file(&mut self, path: &str) -> Result<(), Self::Error> {
+            self.tokens.push(format!("File: {path}"));
+            Ok(())
+        }
+
+        fn url(&mut self, url: &str) -> Result<(), Self::Error> {
+            self.tokens.push(format!("Url: {url}"));
+            Ok(())
+        }
+
+        fn email(&mut self, email: &str) -> Result<(), Self::Error> {
+            self.tokens.push(format!("Email: {email}"));
+            Ok(())
+        }
+
+        fn issue(&mut self, issue: &str) -> Result<(), Self::Error> {
+            self.tokens.push(format!("Issue: {issue}"));
+            Ok(())
+        }
+
+        fn char(&mut self, value: char) -> Result<(), Self::Error> {
+            self.tokens.push(format!("Char: {value:?}"));
+            Ok(())
+        }
+
         fn begin_block(&mut self) -> Result<(), Self::Error> {
             self.tokens.push("BeginBlock".to_string());
             Ok(())
@@ -483,16 +1397,37 @@ mod tests {
             Ok(())
         }
 
-        fn end_path(&mut self) -> Result<(), Self::Error> {
-            self.tokens.push("EndPath".to_string());
+        fn end_path(&mut self, kind: PathKind) -> Result<(), Self::Error> {
+            let kind_str = match kind {
+                PathKind::Path => "EndPath",
+                PathKind::SetPath => "EndSetPath",
+                PathKind::GetPath => "EndGetPath",
+                PathKind::LitPath => "EndLitPath",
+            };
+            self.tokens.push(kind_str.to_string());
+            Ok(())
+        }
+
+        fn begin_paren(&mut self) -> Result<(), Self::Error> {
+            self.tokens.push("BeginParen".to_string());
+            Ok(())
+        }
+
+        fn end_paren(&mut self) -> Result<(), Self::Error> {
+            self.tokens.push("EndParen".to_string());
+            Ok(())
+        }
+
+        fn span(&mut self, span: Range<usize>) -> Result<(), Self::Error> {
+            self.spans.push(span);
             Ok(())
         }
     }
 
     // Helper function to create a parser and run the parse operation
-    fn parse(input: &str) -> Result<SimpleCollector, ParserError<()>> {
+    fn parse(input: &str) -> Result<SimpleCollector, ParserErrorKind<()>> {
         let mut collector = SimpleCollector::default();
-        Parser::parse(input, &mut collector)?;
+        Parser::parse(input, &mut collector).map_err(|error| error.kind)?;
         Ok(collector)
     }
 
@@ -614,6 +1549,354 @@ mod tests {
         assert_eq!(tokens[5], "EndBlock");
     }
 
+    #[test]
+    fn test_money() {
+        let collector = parse("[$12.34 -$0.5 +$7 $0.0001]").unwrap();
+        assert_eq!(
+            collector.tokens,
+            vec![
+                "BeginBlock",
+                "Money: $12.34",
+                "Money: -$0.50",
+                "Money: $7.00",
+                "Money: $0.0001",
+                "EndBlock"
+            ]
+        );
+
+        let result = parse("[$1.23456]");
+        assert!(matches!(
+            result,
+            Err(ParserErrorKind::InvalidMoney(MoneyError::Precision))
+        ));
+        let result = parse("[$]");
+        assert!(matches!(result, Err(ParserErrorKind::UnexpectedChar('$'))));
+        let result = parse("[1$]");
+        assert!(matches!(result, Err(ParserErrorKind::UnexpectedChar('$'))));
+    }
+
+    #[test]
+    fn test_binary() {
+        let collector = parse("[#{DEADbeef} #{} 16#{00 ff} 64#{3q2+7w==} 64#{YQ}]").unwrap();
+        assert_eq!(
+            collector.tokens,
+            vec![
+                "BeginBlock",
+                "Binary: [DE, AD, BE, EF]",
+                "Binary: []",
+                "Binary: [00, FF]",
+                "Binary: [DE, AD, BE, EF]",
+                "Binary: [61]",
+                "EndBlock"
+            ]
+        );
+
+        for invalid in [
+            "[#{ABC}]",
+            "[#{GG}]",
+            "[64#{A}]",
+            "[64#{YR==}]",
+            "[64#{Y===}]",
+        ] {
+            assert!(
+                matches!(parse(invalid), Err(ParserErrorKind::InvalidBinary)),
+                "{invalid}"
+            );
+        }
+        assert!(matches!(parse("[#{AB"), Err(ParserErrorKind::EndOfInput)));
+        assert!(matches!(
+            parse("[16#AB]"),
+            Err(ParserErrorKind::UnexpectedChar('A'))
+        ));
+        assert!(matches!(
+            parse("[32#{AB}]"),
+            Err(ParserErrorKind::UnexpectedChar('#'))
+        ));
+    }
+
+    #[test]
+    fn test_dates_and_times() {
+        let input = "[2026-10-16 16-Oct-2026/10:30:00+2:00 10:30:15.5 -1:30 [1-Jan-2000/0:00Z]]";
+        let collector = parse(input).unwrap();
+        assert_eq!(
+            collector.tokens,
+            vec![
+                "BeginBlock",
+                "Date: 16-Oct-2026",
+                "Date: 16-Oct-2026/10:30+2:00",
+                "Time: 10:30:15.5",
+                "Time: -1:30",
+                "BeginBlock",
+                "Date: 1-Jan-2000/0:00+0:00",
+                "EndBlock",
+                "EndBlock"
+            ]
+        );
+
+        assert!(matches!(
+            parse("[30-Feb-2026]"),
+            Err(ParserErrorKind::InvalidDate(DateError::OutOfRange))
+        ));
+        assert!(matches!(
+            parse("[2026-10]"),
+            Err(ParserErrorKind::InvalidDate(DateError::Invalid))
+        ));
+        assert!(matches!(
+            parse("[10:75]"),
+            Err(ParserErrorKind::InvalidTime(DateError::OutOfRange))
+        ));
+        assert!(matches!(
+            parse("[1.5:30]"),
+            Err(ParserErrorKind::UnexpectedChar(':'))
+        ));
+    }
+
+    #[test]
+    fn test_tuples_and_pairs() {
+        let collector = parse("[1.2.3 192.168.0.1 10x20 -5X+5 [0.0.0]]").unwrap();
+        assert_eq!(
+            collector.tokens,
+            vec![
+                "BeginBlock",
+                "Tuple: 1.2.3",
+                "Tuple: 192.168.0.1",
+                "Pair: 10x20",
+                "Pair: -5x5",
+                "BeginBlock",
+                "Tuple: 0.0.0",
+                "EndBlock",
+                "EndBlock"
+            ]
+        );
+
+        assert!(matches!(
+            parse("[1.2.256]"),
+            Err(ParserErrorKind::InvalidTuple(TupleError::OutOfRange))
+        ));
+        assert!(matches!(
+            parse("[1..2]"),
+            Err(ParserErrorKind::InvalidTuple(TupleError::Invalid))
+        ));
+        assert!(matches!(
+            parse("[10x]"),
+            Err(ParserErrorKind::InvalidPair(TupleError::Invalid))
+        ));
+        assert!(matches!(
+            parse("[1.5x2]"),
+            Err(ParserErrorKind::UnexpectedChar('x'))
+        ));
+    }
+
+    #[test]
+    fn test_brace_strings() {
+        let input = "[{multi\n  line} {a {nested} b} {^/^-^(41)^^^{} {say \"hi\"} {} x]";
+        let collector = parse(input).unwrap();
+        assert_eq!(
+            collector.tokens,
+            vec![
+                "BeginBlock",
+                "String: multi\n  line",
+                "String: a {nested} b",
+                "String: \n\tA^{",
+                "String: say \"hi\"",
+                "String: ",
+                "Word: x",
+                "EndBlock"
+            ]
+        );
+
+        assert!(matches!(
+            parse("[{a {b}]"),
+            Err(ParserErrorKind::EndOfInput)
+        ));
+        assert!(matches!(
+            parse("[{a ^1}]"),
+            Err(ParserErrorKind::UnexpectedChar('1'))
+        ));
+    }
+
+    #[test]
+    fn test_chars() {
+        let input = r#"[#"a" #"^/" #"^(41)" #"^-" #"^^" #"^"" #"^A" #"é"]"#;
+        let collector = parse(input).unwrap();
+        assert_eq!(
+            collector.tokens,
+            vec![
+                "BeginBlock",
+                "Char: 'a'",
+                "Char: '\\n'",
+                "Char: 'A'",
+                "Char: '\\t'",
+                "Char: '^'",
+                "Char: '\"'",
+                "Char: '\\u{1}'",
+                "Char: 'é'",
+                "EndBlock"
+            ]
+        );
+
+        for invalid in [
+            r#"[#""]"#,
+            r#"[#"ab"]"#,
+            r#"[#"^(D800)"]"#,
+            r#"[#"^(xyz)"]"#,
+        ] {
+            assert!(
+                matches!(parse(invalid), Err(ParserErrorKind::InvalidChar)),
+                "{invalid}"
+            );
+        }
+        assert!(matches!(
+            parse(r#"[#"^1"]"#),
+            Err(ParserErrorKind::UnexpectedChar('1'))
+        ));
+        assert!(matches!(
+            parse(r#"[#"a]"#),
+            Err(ParserErrorKind::EndOfInput)
+        ));
+    }
+
+    #[test]
+    fn test_files_urls_emails_and_issues() {
+        let input = "[%data/users.csv http://host/x?a=1 a@b.com john.doe@example.com 1st@x.org #abc-123 [%a]]";
+        let collector = parse(input).unwrap();
+        assert_eq!(
+            collector.tokens,
+            vec![
+                "BeginBlock",
+                "File: data/users.csv",
+                "Url: http://host/x?a=1",
+                "Email: a@b.com",
+                "Email: john.doe@example.com",
+                "Email: 1st@x.org",
+                "Issue: abc-123",
+                "BeginBlock",
+                "File: a",
+                "EndBlock",
+                "EndBlock"
+            ]
+        );
+
+        // a set-word is still a set-word
+        let collector = parse("[http: 1]").unwrap();
+        assert_eq!(collector.tokens[1], "SetWord: http");
+
+        assert!(matches!(
+            parse("[% ]"),
+            Err(ParserErrorKind::UnexpectedChar('%'))
+        ));
+        assert!(matches!(
+            parse("[#]"),
+            Err(ParserErrorKind::UnexpectedChar('#'))
+        ));
+        assert!(matches!(
+            parse("[http://]"),
+            Err(ParserErrorKind::UnexpectedChar(':'))
+        ));
+        for invalid in ["[a@]", "[a@b@c]", "[a.b@]"] {
+            assert!(
+                matches!(parse(invalid), Err(ParserErrorKind::InvalidEmail)),
+                "{invalid}"
+            );
+        }
+        assert!(matches!(
+            parse("[a.b]"),
+            Err(ParserErrorKind::UnexpectedChar('.'))
+        ));
+    }
+
+    #[test]
+    fn test_wide_numbers() {
+        let input = "[9223372036854775807 -9223372036854775808 3000000000]";
+        let collector = parse(input).unwrap();
+        assert_eq!(
+            collector.tokens,
+            vec![
+                "BeginBlock",
+                "Integer: 9223372036854775807",
+                "Integer: -9223372036854775808",
+                "Integer: 3000000000",
+                "EndBlock"
+            ]
+        );
+
+        struct Floats(Vec<f64>);
+        impl Collector for Floats {
+            type Error = ();
+            fn string(&mut self, _: &str) -> Result<(), ()> {
+                Ok(())
+            }
+            fn word(&mut self, _: WordKind, _: &str) -> Result<(), ()> {
+                Ok(())
+            }
+            fn integer(&mut self, _: i64) -> Result<(), ()> {
+                Ok(())
+            }
+            fn float(&mut self, value: f64) -> Result<(), ()> {
+                self.0.push(value);
+                Ok(())
+            }
+            fn money(&mut self, _: Money) -> Result<(), ()> {
+                Ok(())
+            }
+            fn binary(&mut self, _: &[u8]) -> Result<(), ()> {
+                Ok(())
+            }
+            fn date(&mut self, _: Date) -> Result<(), ()> {
+                Ok(())
+            }
+            fn time(&mut self, _: Time) -> Result<(), ()> {
+                Ok(())
+            }
+            fn tuple(&mut self, _: Tuple) -> Result<(), ()> {
+                Ok(())
+            }
+            fn pair(&mut self, _: Pair) -> Result<(), ()> {
+                Ok(())
+            }
+            fn file(&mut self, _: &str) -> Result<(), ()> {
+                Ok(())
+            }
+            fn url(&mut self, _: &str) -> Result<(), ()> {
+                Ok(())
+            }
+            fn email(&mut self, _: &str) -> Result<(), ()> {
+                Ok(())
+            }
+            fn issue(&mut self, _: &str) -> Result<(), ()> {
+                Ok(())
+            }
+            fn char(&mut self, _: char) -> Result<(), ()> {
+                Ok(())
+            }
+            fn begin_block(&mut self) -> Result<(), ()> {
+                Ok(())
+            }
+            fn end_block(&mut self) -> Result<(), ()> {
+                Ok(())
+            }
+            fn begin_path(&mut self) -> Result<(), ()> {
+                Ok(())
+            }
+            fn end_path(&mut self, _: PathKind) -> Result<(), ()> {
+                Ok(())
+            }
+            fn begin_paren(&mut self) -> Result<(), ()> {
+                Ok(())
+            }
+            fn end_paren(&mut self) -> Result<(), ()> {
+                Ok(())
+            }
+        }
+        // floats round like Rust's own literals
+        let mut floats = Floats(Vec::new());
+        Parser::parse("0.1 -1.0000000000000002 16777217.0", &mut floats).unwrap();
+        assert_eq!(floats.0, [0.1, -1.0 - f64::EPSILON, 16777217.0]);
+
+        let huge = format!("[1{}.0]", "0".repeat(400));
+        assert!(matches!(parse(&huge), Err(ParserErrorKind::FloatOverflow)));
+    }
+
     #[test]
     fn test_words() {
         let input = "[word set-word: :get-word]";
@@ -632,6 +1915,24 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_operator_words() {
+        let collector = parse("[= <> < <= tail?]").unwrap();
+
+        assert_eq!(
+            collector.tokens,
+            vec![
+                "BeginBlock",
+                "Word: =",
+                "Word: <>",
+                "Word: <",
+                "Word: <=",
+                "Word: tail?",
+                "EndBlock"
+            ]
+        );
+    }
+
     #[test]
     fn test_nested_blocks() {
         let input = "[outer [inner1 [deep]] [inner2]]";
@@ -681,6 +1982,97 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_path_kinds() {
+        let input = "['name :obj/field obj/field: 'obj/field blk/1: blk/(i): :f]";
+        let collector = parse(input).unwrap();
+        assert_eq!(
+            collector.tokens,
+            vec![
+                "BeginBlock",
+                "LitWord: name",
+                "BeginPath",
+                "Word: obj",
+                "Word: field",
+                "EndGetPath",
+                "BeginPath",
+                "Word: obj",
+                "Word: field",
+                "EndSetPath",
+                "BeginPath",
+                "Word: obj",
+                "Word: field",
+                "EndLitPath",
+                "BeginPath",
+                "Word: blk",
+                "Integer: 1",
+                "EndSetPath",
+                "BeginPath",
+                "Word: blk",
+                "BeginParen",
+                "Word: i",
+                "EndParen",
+                "EndSetPath",
+                "GetWord: f",
+                "EndBlock"
+            ]
+        );
+
+        for invalid in ["[:obj/field:]", "['a:]", "[:a:]", "['obj/(1):]"] {
+            assert!(
+                matches!(parse(invalid), Err(ParserErrorKind::UnexpectedChar(':'))),
+                "{invalid}"
+            );
+        }
+        assert!(matches!(parse("[']"), Err(ParserErrorKind::EmptyWord)));
+    }
+
+    #[test]
+    fn test_parens() {
+        let input = "[(1 + 2) * 3 blk/(i + 1)/x f/(g) [a (b/c)] ()]";
+        let collector = parse(input).unwrap();
+        assert_eq!(
+            collector.tokens,
+            vec![
+                "BeginBlock",
+                "BeginParen",
+                "Integer: 1",
+                "Word: +",
+                "Integer: 2",
+                "EndParen",
+                "Word: *",
+                "Integer: 3",
+                "BeginPath",
+                "Word: blk",
+                "BeginParen",
+                "Word: i",
+                "Word: +",
+                "Integer: 1",
+                "EndParen",
+                "Word: x",
+                "EndPath",
+                "BeginPath",
+                "Word: f",
+                "BeginParen",
+                "Word: g",
+                "EndParen",
+                "EndPath",
+                "BeginBlock",
+                "Word: a",
+                "BeginParen",
+                "BeginPath",
+                "Word: b",
+                "Word: c",
+                "EndPath",
+                "EndParen",
+                "EndBlock",
+                "BeginParen",
+                "EndParen",
+                "EndBlock"
+            ]
+        );
+    }
+
     #[test]
     fn test_mixed_tokens() {
         let input = r#"[
@@ -725,27 +2117,30 @@ mod tests {
     fn test_error_conditions() {
         // Invalid escape sequence
         let result = parse(r#"["invalid \z escape"]"#);
-        assert!(matches!(result, Err(ParserError::UnexpectedChar('z'))));
+        assert!(matches!(result, Err(ParserErrorKind::UnexpectedChar('z'))));
 
         // Unclosed string
         let result = parse(r#"["unclosed string]"#);
-        assert!(matches!(result, Err(ParserError::EndOfInput)));
+        assert!(matches!(result, Err(ParserErrorKind::EndOfInput)));
 
         // Empty word (error)
         let result = parse("[:]");
-        assert!(matches!(result, Err(ParserError::EmptyWord)));
+        assert!(matches!(result, Err(ParserErrorKind::EmptyWord)));
 
-        // Integer overflow (if we try to parse a number larger than i32::MAX)
-        let result = parse("[99999999999]");
-        assert!(matches!(result, Err(ParserError::IntegerOverflow)));
+        // Integer overflow (if we try to parse a number larger than i64::MAX)
+        let result = parse("[9223372036854775808]");
+        assert!(matches!(result, Err(ParserErrorKind::IntegerOverflow)));
 
-        // Invalid float format (multiple decimal points)
-        let result = parse("[3.14.159]");
-        assert!(matches!(result, Err(ParserError::UnexpectedChar('.'))));
+        // Multiple decimal points make a tuple, whose items are bytes
+        let result = parse("[3.14.1590]");
+        assert!(matches!(
+            result,
+            Err(ParserErrorKind::InvalidTuple(TupleError::Invalid))
+        ));
 
         // Numbers must be followed by whitespace or closing bracket
         let result = parse("[12abc]");
-        assert!(matches!(result, Err(ParserError::UnexpectedChar('a'))));
+        assert!(matches!(result, Err(ParserErrorKind::UnexpectedChar('a'))));
     }
 
     // Static parse methods test from parser.rs
@@ -788,4 +2183,212 @@ mod tests {
             ]
         );
     }
+
+    #[test]
+    fn test_error_locations() {
+        let locate = |input: &str| {
+            let mut collector = SimpleCollector::default();
+            let error = Parser::parse(input, &mut collector).unwrap_err();
+            let location = error.location;
+            (location.line, location.column, location.offset)
+        };
+        assert_eq!(locate("[a b ~]"), (1, 6, 5));
+        assert_eq!(locate("[a\n  1.2.3.4.5.6.7.8\n]"), (2, 3, 5));
+        assert_eq!(locate("[a\n  {é\n  b"), (3, 4, 12));
+        assert_eq!(locate("[\"é\" 12z]"), (1, 8, 8));
+        assert_eq!(locate("[:obj/(1):]"), (1, 10, 9));
+
+        let mut collector = SimpleCollector::default();
+        let error = Parser::parse("[x\n y: $1.23456]", &mut collector).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "invalid money: money has more than 4 decimal places at line 2, column 5"
+        );
+        assert_eq!(
+            Location::new("a\nbé\nc", 5),
+            Location {
+                line: 2,
+                column: 3,
+                offset: 5
+            }
+        );
+    }
+
+    #[test]
+    fn test_spans() {
+        let input = "[word \"str\" 12] (a/b/(c): 1.5)\n:x/1 'y";
+        let mut collector = SimpleCollector::default();
+        Parser::parse(input, &mut collector).unwrap();
+        let spans: Vec<_> = collector
+            .spans
+            .iter()
+            .map(|span| &input[span.clone()])
+            .collect();
+        assert_eq!(
+            spans,
+            vec![
+                "word",
+                "\"str\"",
+                "12",
+                "[word \"str\" 12]",
+                "a",
+                "b",
+                "c",
+                "(c)",
+                "a/b/(c):",
+                "1.5",
+                "(a/b/(c): 1.5)",
+                "x",
+                "1",
+                ":x/1",
+                "'y",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_recovering() {
+        let input = "[a 12z b ~c [1.2.3.4.5.6.7.8] obj/x~y d\n#\"ab\" (e:)]";
+        let mut collector = SimpleCollector::default();
+        let errors = Parser::parse_recovering(input, &mut collector).unwrap_err();
+        assert_eq!(
+            collector.tokens,
+            vec![
+                "BeginBlock",
+                "Word: a",
+                "Word: b",
+                "BeginBlock",
+                "EndBlock",
+                "BeginPath",
+                "Word: obj",
+                "EndPath",
+                "Word: d",
+                "BeginParen",
+                "SetWord: e",
+                "EndParen",
+                "EndBlock",
+            ]
+        );
+        let errors: Vec<_> = errors
+            .iter()
+            .map(|error| {
+                (
+                    error.kind.to_string(),
+                    error.location.line,
+                    error.location.column,
+                )
+            })
+            .collect();
+        assert_eq!(
+            errors,
+            vec![
+                ("unexpected character: `z`".to_string(), 1, 6),
+                ("unexpected character: `~`".to_string(), 1, 10),
+                (
+                    "invalid tuple: tuple or pair item out of range".to_string(),
+                    1,
+                    14
+                ),
+                ("unexpected character: `~`".to_string(), 1, 36),
+                ("invalid character".to_string(), 2, 1),
+            ]
+        );
+
+        // a bracket inside an unterminated string does not close the block
+        let mut collector = SimpleCollector::default();
+        let errors = Parser::parse_block_recovering("a \"b]", &mut collector).unwrap_err();
+        assert!(matches!(
+            errors[..],
+            [ParserError {
+                kind: ParserErrorKind::EndOfInput,
+                ..
+            }]
+        ));
+        assert_eq!(collector.tokens, vec!["BeginBlock", "Word: a", "EndBlock"]);
+
+        let mut collector = SimpleCollector::default();
+        assert!(Parser::parse_block_recovering("a [b]", &mut collector).is_ok());
+
+        // the whitespace ending a bad literal is not skipped with it, and a
+        // bracket in a string with a bad escape closes nothing
+        let check = |input: &str, tokens: &[&str], expected: &[(&str, usize)]| {
+            let mut collector = SimpleCollector::default();
+            let errors = Parser::parse_recovering(input, &mut collector).unwrap_err();
+            let errors: Vec<_> = errors
+                .iter()
+                .map(|error| (error.kind.to_string(), error.location.column))
+                .collect();
+            let expected: Vec<_> = expected
+                .iter()
+                .map(|(message, column)| (message.to_string(), *column))
+                .collect();
+            assert_eq!(collector.tokens, tokens, "{input}");
+            assert_eq!(errors, expected, "{input}");
+        };
+        check(
+            "[2026-13-01 y] z",
+            &["BeginBlock", "Word: y", "EndBlock", "Word: z"],
+            &[("invalid date: date or time out of range", 2)],
+        );
+        check(
+            "[a 1.2.3.999 b] c",
+            &["BeginBlock", "Word: a", "Word: b", "EndBlock", "Word: c"],
+            &[("invalid tuple: tuple or pair item out of range", 4)],
+        );
+        check("foo@ x", &["Word: x"], &[("invalid email", 1)]);
+        check(
+            "http:// x",
+            &["Word: x"],
+            &[("unexpected character: `:`", 5)],
+        );
+        check("#\"^(zz)\" x", &["Word: x"], &[("invalid character", 1)]);
+        check("{ab ^(zz) } x", &["Word: x"], &[("invalid character", 1)]);
+        check(
+            "[a)",
+            &["BeginBlock", "Word: a", "EndBlock"],
+            &[("unexpected character: `)`", 3), ("unclosed `[`", 1)],
+        );
+        check(
+            "a/(b",
+            &[
+                "BeginPath",
+                "Word: a",
+                "BeginParen",
+                "Word: b",
+                "EndParen",
+                "EndPath",
+            ],
+            &[("unclosed `(`", 3)],
+        );
+
+        // unbalanced brackets
+        let mut collector = SimpleCollector::default();
+        let errors = Parser::parse_recovering("[a", &mut collector).unwrap_err();
+        assert!(matches!(
+            errors[..],
+            [ParserError {
+                kind: ParserErrorKind::Unclosed('['),
+                ..
+            }]
+        ));
+        assert_eq!(collector.tokens, vec!["BeginBlock", "Word: a", "EndBlock"]);
+        let mut collector = SimpleCollector::default();
+        let errors = Parser::parse_block_recovering("a] b", &mut collector).unwrap_err();
+        assert!(matches!(
+            errors[..],
+            [ParserError {
+                kind: ParserErrorKind::UnexpectedChar(']'),
+                ..
+            }]
+        ));
+        assert_eq!(
+            collector.tokens,
+            vec!["BeginBlock", "Word: a", "Word: b", "EndBlock"]
+        );
+        let mut collector = SimpleCollector::default();
+        let error = Parser::parse("[a [b] c", &mut collector).unwrap_err();
+        assert_eq!(error.to_string(), "unclosed `[` at line 1, column 1");
+        let mut collector = SimpleCollector::default();
+        assert!(Parser::parse("a)", &mut collector).is_err());
+    }
 }
@@ -1,26 +1,187 @@
 // Rebel™ © 2025 Huly Labs • https://hulylabs.com • SPDX-License-Identifier: MIT
 
-use crate::mem::{Func, Value};
+use crate::date::{Date, DateError, Time};
+use crate::mem::{Func, Memory, MemoryError, Offset, Series, Value, char_offset};
+use crate::money::Money;
+use crate::tuple::{Pair, Tuple};
 use crate::vm::{NativeDescriptor, Process, VmError};
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::hash::{DefaultHasher, Hasher};
+
+/// Operands of an arithmetic native, converted to their common type: two
+/// integers stay integers, money with any number is money, and the rest
+/// are floats. A float on the right of money is kept as it is, to divide
+/// by it exactly. A date or a time on the left keeps its type, with a right
+/// operand of days or seconds as an integer or a float. Tuples and pairs
+/// work item by item, with a number applying to every item.
+enum Operands {
+    Int(i64, i64),
+    Float(f64, f64),
+    Money(Money, Money),
+    MoneyFloat(Money, f64),
+    Days(Date, i64),
+    DateTime(Date, Time),
+    Dates(Date, Date),
+    Times(Time, Time),
+    TimeNumber(Time, f64),
+    Tuples(Tuple, Tuple),
+    TupleNumber(Tuple, f64),
+    Pairs(Pair, Pair),
+}
+
+impl Operands {
+    fn pop(process: &mut Process) -> Result<Self, VmError> {
+        let &[a, b] = process.get_stack_mut().pop_n()?;
+        let rounding = process.rounding();
+        let money = |value: Value| match value.kind() {
+            Value::INT => Money::from_int(value.as_int()?).map_err(VmError::from),
+            Value::FLOAT => Money::from_float(value.as_float()?, rounding).map_err(VmError::from),
+            _ => value.as_money().map_err(VmError::from),
+        };
+        let float = |value: Value| match value.kind() {
+            Value::INT => Ok(value.as_int()? as f64),
+            _ => value.as_float(),
+        };
+        let pair = |value: Value| match value.kind() {
+            Value::INT => Pair::splat(value.as_int()?).ok_or(VmError::IntegerOverflow),
+            _ => value.as_pair().map_err(VmError::from),
+        };
+        match (a.kind(), b.kind()) {
+            (Value::INT, Value::INT) => Ok(Operands::Int(a.as_int()?, b.as_int()?)),
+            (Value::TUPLE, Value::TUPLE) => Ok(Operands::Tuples(a.as_tuple()?, b.as_tuple()?)),
+            (Value::TUPLE, _) => Ok(Operands::TupleNumber(a.as_tuple()?, float(b)?)),
+            (Value::PAIR, _) | (_, Value::PAIR) => Ok(Operands::Pairs(pair(a)?, pair(b)?)),
+            (Value::DATE, Value::INT) => Ok(Operands::Days(a.as_date()?, b.as_int()?)),
+            (Value::DATE, Value::TIME) => Ok(Operands::DateTime(a.as_date()?, b.as_time()?)),
+            (Value::DATE, _) => Ok(Operands::Dates(a.as_date()?, b.as_date()?)),
+            (Value::TIME, Value::TIME) => Ok(Operands::Times(a.as_time()?, b.as_time()?)),
+            (Value::TIME, _) => Ok(Operands::TimeNumber(a.as_time()?, float(b)?)),
+            (Value::MONEY, Value::FLOAT) => Ok(Operands::MoneyFloat(a.as_money()?, b.as_float()?)),
+            (Value::MONEY, _) | (_, Value::MONEY) => Ok(Operands::Money(money(a)?, money(b)?)),
+            _ => Ok(Operands::Float(float(a)?, float(b)?)),
+        }
+    }
+}
 
 fn add(process: &mut Process) -> Result<(), VmError> {
-    let [va, vb] = process.get_stack_mut().pop_n()?;
-    let a = va.as_int()?;
-    let b = vb.as_int()?;
-    let result = a.checked_add(b).ok_or(VmError::IntegerOverflow)?;
+    let rounding = process.rounding();
+    let result = match Operands::pop(process)? {
+        Operands::Int(a, b) => Value::int(a.checked_add(b).ok_or(VmError::IntegerOverflow)?),
+        Operands::Float(a, b) => Value::float(a + b),
+        Operands::Money(a, b) => Value::money(a.checked_add(b)?),
+        Operands::MoneyFloat(a, b) => Value::money(a.checked_add(Money::from_float(b, rounding)?)?),
+        Operands::Days(date, days) => Value::date(date.add_days(days)?),
+        Operands::DateTime(date, time) => Value::date(date.add_time(time)?),
+        Operands::Times(a, b) => Value::time(a.checked_add(b)?),
+        Operands::TimeNumber(time, seconds) => {
+            Value::time(time.checked_add(Time::from_seconds(seconds)?)?)
+        }
+        Operands::Tuples(a, b) => Value::tuple(a.zip(b, |x, y| x + y)),
+        Operands::TupleNumber(tuple, n) => Value::tuple(tuple.map(|x| x + n)),
+        Operands::Pairs(a, b) => Value::pair(a.checked_add(b).ok_or(VmError::IntegerOverflow)?),
+        Operands::Dates(..) => return Err(MemoryError::TypeMismatch.into()),
+    };
+    process.get_stack_mut().push(result).map_err(Into::into)
+}
+
+/// Two dates without a time are days apart, other dates a time apart
+fn subtract(process: &mut Process) -> Result<(), VmError> {
+    let rounding = process.rounding();
+    let result = match Operands::pop(process)? {
+        Operands::Int(a, b) => Value::int(a.checked_sub(b).ok_or(VmError::IntegerOverflow)?),
+        Operands::Float(a, b) => Value::float(a - b),
+        Operands::Money(a, b) => Value::money(a.checked_sub(b)?),
+        Operands::MoneyFloat(a, b) => Value::money(a.checked_sub(Money::from_float(b, rounding)?)?),
+        Operands::Days(date, days) => {
+            let days = days.checked_neg().ok_or(DateError::OutOfRange)?;
+            Value::date(date.add_days(days)?)
+        }
+        Operands::DateTime(date, time) => {
+            Value::date(date.add_time(Time::default().checked_sub(time)?)?)
+        }
+        Operands::Dates(a, b) if a.time().is_none() && b.time().is_none() => {
+            Value::int(a.days_since(&b))
+        }
+        Operands::Dates(a, b) => Value::time(a.since(&b)?),
+        Operands::Times(a, b) => Value::time(a.checked_sub(b)?),
+        Operands::TimeNumber(time, seconds) => {
+            Value::time(time.checked_sub(Time::from_seconds(seconds)?)?)
+        }
+        Operands::Tuples(a, b) => Value::tuple(a.zip(b, |x, y| x - y)),
+        Operands::TupleNumber(tuple, n) => Value::tuple(tuple.map(|x| x - n)),
+        Operands::Pairs(a, b) => Value::pair(a.checked_sub(b).ok_or(VmError::IntegerOverflow)?),
+    };
+    process.get_stack_mut().push(result).map_err(Into::into)
+}
+
+fn multiply(process: &mut Process) -> Result<(), VmError> {
+    let rounding = process.rounding();
+    let result = match Operands::pop(process)? {
+        Operands::Int(a, b) => Value::int(a.checked_mul(b).ok_or(VmError::IntegerOverflow)?),
+        Operands::Float(a, b) => Value::float(a * b),
+        Operands::Money(a, b) => Value::money(a.multiply(b, rounding)?),
+        Operands::MoneyFloat(a, b) => Value::money(a.multiply_float(b, rounding)?),
+        Operands::TimeNumber(time, factor) => Value::time(time.scale(factor)?),
+        Operands::Tuples(a, b) => Value::tuple(a.zip(b, |x, y| x * y)),
+        Operands::TupleNumber(tuple, n) => Value::tuple(tuple.map(|x| x * n)),
+        Operands::Pairs(a, b) => Value::pair(a.checked_mul(b).ok_or(VmError::IntegerOverflow)?),
+        _ => return Err(MemoryError::TypeMismatch.into()),
+    };
+    process.get_stack_mut().push(result).map_err(Into::into)
+}
+
+/// Integers that do not divide evenly give a float, and so do two times
+fn divide(process: &mut Process) -> Result<(), VmError> {
+    let rounding = process.rounding();
+    let result = match Operands::pop(process)? {
+        Operands::Int(_, 0) => return Err(VmError::DivisionByZero),
+        Operands::Int(a, b) => match a.checked_rem(b).ok_or(VmError::IntegerOverflow)? {
+            0 => Value::int(a.checked_div(b).ok_or(VmError::IntegerOverflow)?),
+            _ => Value::float(a as f64 / b as f64),
+        },
+        Operands::Float(_, 0.0) | Operands::TimeNumber(_, 0.0) | Operands::TupleNumber(_, 0.0) => {
+            return Err(VmError::DivisionByZero);
+        }
+        Operands::Float(a, b) => Value::float(a / b),
+        Operands::Money(a, b) => Value::money(a.divide(b, rounding)?),
+        Operands::MoneyFloat(a, b) => Value::money(a.divide_float(b, rounding)?),
+        Operands::TimeNumber(time, divisor) => Value::time(time.scale(1.0 / divisor)?),
+        Operands::Times(_, b) if b.nanos() == 0 => return Err(VmError::DivisionByZero),
+        Operands::Times(a, b) => Value::float(a.nanos() as f64 / b.nanos() as f64),
+        // a shorter divisor is padded with zeros
+        Operands::Tuples(a, b) if b.items().len() < a.items().len() || b.items().contains(&0) => {
+            return Err(VmError::DivisionByZero);
+        }
+        Operands::Tuples(a, b) => Value::tuple(a.zip(b, |x, y| x / y)),
+        Operands::TupleNumber(tuple, n) => Value::tuple(tuple.map(|x| x / n)),
+        Operands::Pairs(_, b) if b.x() == 0 || b.y() == 0 => return Err(VmError::DivisionByZero),
+        Operands::Pairs(a, b) => Value::pair(a.checked_div(b).ok_or(VmError::IntegerOverflow)?),
+        _ => return Err(MemoryError::TypeMismatch.into()),
+    };
+    process.get_stack_mut().push(result).map_err(Into::into)
+}
+
+fn to_money(process: &mut Process) -> Result<(), VmError> {
+    let &[value] = process.get_stack_mut().pop_n()?;
+    let result = match value.kind() {
+        Value::INT => Money::from_int(value.as_int()?)?,
+        Value::FLOAT => Money::from_float(value.as_float()?, process.rounding())?,
+        Value::STRING => process.memory().string_at(value)?.parse::<Money>()?,
+        _ => value.as_money()?,
+    };
     process
         .get_stack_mut()
-        .push(Value::int(result))
+        .push(Value::money(result))
         .map_err(Into::into)
 }
 
 fn lt(process: &mut Process) -> Result<(), VmError> {
-    let [va, vb] = process.get_stack_mut().pop_n()?;
-    let a = va.as_int()?;
-    let b = vb.as_int()?;
+    let &[a, b] = process.get_stack_mut().pop_n()?;
+    let result = process.memory().compare_like(a, b)? == Ordering::Less;
     process
         .get_stack_mut()
-        .push(Value::bool(a < b))
+        .push(Value::bool(result))
         .map_err(Into::into)
 }
 
@@ -39,12 +200,19 @@ fn func(process: &mut Process) -> Result<(), VmError> {
     let spec_block = spec.as_block()?;
     let body_block = body.as_block()?;
 
-    let arity = process.memory().len(spec_block)?;
-    let body_bindings = process.get_binding(body_block)?;
+    let parent = process.context();
+    let memory = process.memory_mut();
+    let arity = memory.len(spec_block)?;
+    let context = memory.alloc_context(parent, arity as usize)?;
+    for i in 0..arity {
+        let param = memory.get_item(spec_block, i).copied()?;
+        if !param.is_word() {
+            return Err(MemoryError::TypeMismatch.into());
+        }
+        memory.add_context_word(context, param.data())?;
+    }
 
-    let func = process
-        .memory_mut()
-        .alloc_struct(Func::new(arity, body_bindings))?;
+    let func = memory.alloc_struct(Func::new(context, arity, body_block))?;
 
     process
         .get_stack_mut()
@@ -52,6 +220,658 @@ fn func(process: &mut Process) -> Result<(), VmError> {
         .map_err(Into::into)
 }
 
+/// Copies a block, replacing each paren with its value. A block value is
+/// spliced into the copy as its items.
+fn compose(process: &mut Process) -> Result<(), VmError> {
+    let &[block] = process.get_stack_mut().pop_n()?;
+    let len = process.memory().items_at(block)?.len();
+    let result = process.memory_mut().alloc::<Value>(len)?;
+    // both stay on the stack while parens run, so they are not collected
+    process.get_stack_mut().push(block)?;
+    process.get_stack_mut().push(Value::block(result))?;
+    for i in 0..len {
+        let Some(&item) = process.memory().items_at(block)?.get(i) else {
+            break;
+        };
+        if item.is_paren() {
+            let code = process.get_binding(item.as_paren()?)?;
+            let value = process.eval(code)?;
+            let memory = process.memory_mut();
+            if value.is_block() {
+                for value in memory.items_at(value)?.to_vec() {
+                    memory.push(result, value)?;
+                }
+            } else {
+                memory.push(result, value)?;
+            }
+        } else {
+            process.memory_mut().push(result, item)?;
+        }
+    }
+    let &[_, result] = process.get_stack_mut().pop_n()?;
+    process.get_stack_mut().push(result).map_err(Into::into)
+}
+
+fn recycle(process: &mut Process) -> Result<(), VmError> {
+    let released = process.collect_garbage()?;
+    process
+        .get_stack_mut()
+        .push(Value::int(released as i64))
+        .map_err(Into::into)
+}
+
+fn head(process: &mut Process) -> Result<(), VmError> {
+    let &[series] = process.get_stack_mut().pop_n()?;
+    let result = process.memory().head(series)?;
+    process.get_stack_mut().push(result).map_err(Into::into)
+}
+
+fn tail(process: &mut Process) -> Result<(), VmError> {
+    let &[series] = process.get_stack_mut().pop_n()?;
+    let result = process.memory().tail(series)?;
+    process.get_stack_mut().push(result).map_err(Into::into)
+}
+
+fn next(process: &mut Process) -> Result<(), VmError> {
+    let &[series] = process.get_stack_mut().pop_n()?;
+    let result = process.memory().next(series)?;
+    process.get_stack_mut().push(result).map_err(Into::into)
+}
+
+fn back(process: &mut Process) -> Result<(), VmError> {
+    let &[series] = process.get_stack_mut().pop_n()?;
+    let result = process.memory().back(series)?;
+    process.get_stack_mut().push(result).map_err(Into::into)
+}
+
+fn skip(process: &mut Process) -> Result<(), VmError> {
+    let &[series, offset] = process.get_stack_mut().pop_n()?;
+    let result = process.memory().skip(series, offset.as_int()?)?;
+    process.get_stack_mut().push(result).map_err(Into::into)
+}
+
+fn at(process: &mut Process) -> Result<(), VmError> {
+    let &[series, index] = process.get_stack_mut().pop_n()?;
+    let result = process.memory().at(series, index.as_int()?)?;
+    process.get_stack_mut().push(result).map_err(Into::into)
+}
+
+fn is_head(process: &mut Process) -> Result<(), VmError> {
+    let &[series] = process.get_stack_mut().pop_n()?;
+    let result = process.memory().is_head(series)?;
+    process
+        .get_stack_mut()
+        .push(Value::bool(result))
+        .map_err(Into::into)
+}
+
+fn is_tail(process: &mut Process) -> Result<(), VmError> {
+    let &[series] = process.get_stack_mut().pop_n()?;
+    let result = process.memory().is_tail(series)?;
+    process
+        .get_stack_mut()
+        .push(Value::bool(result))
+        .map_err(Into::into)
+}
+
+fn index_of(process: &mut Process) -> Result<(), VmError> {
+    let &[series] = process.get_stack_mut().pop_n()?;
+    let index = process.memory().index_of(series)?;
+    process
+        .get_stack_mut()
+        .push(Value::int(index as i64 + 1))
+        .map_err(Into::into)
+}
+
+fn length_of(process: &mut Process) -> Result<(), VmError> {
+    let &[series] = process.get_stack_mut().pop_n()?;
+    let result = process.memory().length_of(series)?;
+    process
+        .get_stack_mut()
+        .push(Value::int(result as i64))
+        .map_err(Into::into)
+}
+
+/// Items `value` stands for when inserted into `series`: the items of a
+/// block from its position, or the value itself, for blocks, paths and
+/// parens; the text of any string, or a character, for strings, files,
+/// URLs, emails and issues; the bytes of a binary or a string, or a byte
+/// given as an integer, for binaries.
+enum Items {
+    Values(Vec<Value>),
+    Text(String),
+    Bytes(Vec<u8>),
+}
+
+impl Items {
+    fn new(memory: &Memory, series: Value, value: Value) -> Result<Self, MemoryError> {
+        match series.kind() {
+            _ if series.is_any_block() && value.is_block() => {
+                Ok(Items::Values(memory.items_at(value)?.to_vec()))
+            }
+            _ if series.is_any_block() => Ok(Items::Values(vec![value])),
+            _ if series.is_any_string() && value.is_char() => {
+                Ok(Items::Text(value.as_char()?.to_string()))
+            }
+            _ if series.is_any_string() => Ok(Items::Text(memory.string_at(value)?.to_string())),
+            Value::BINARY if value.is_int() => {
+                let byte = u8::try_from(value.as_int()?).map_err(|_| MemoryError::OutOfBounds)?;
+                Ok(Items::Bytes(vec![byte]))
+            }
+            Value::BINARY => Ok(Items::Bytes(memory.bytes_at(value)?.to_vec())),
+            _ => Err(MemoryError::TypeMismatch),
+        }
+    }
+
+    /// Number of items of the series, bytes for text
+    fn len(&self) -> Offset {
+        match self {
+            Items::Values(values) => values.len() as Offset,
+            Items::Text(text) => text.len() as Offset,
+            Items::Bytes(bytes) => bytes.len() as Offset,
+        }
+    }
+
+    /// Inserts the items at `offset` in the items of `series`
+    fn insert(
+        &self,
+        memory: &mut Memory,
+        series: Value,
+        offset: Offset,
+    ) -> Result<(), MemoryError> {
+        match self {
+            Items::Values(values) => memory.insert(Series::new(series.data()), offset, values),
+            Items::Text(text) => memory.insert(Series::new(series.data()), offset, text.as_bytes()),
+            Items::Bytes(bytes) => memory.insert(Series::new(series.data()), offset, bytes),
+        }
+    }
+}
+
+fn insert(process: &mut Process) -> Result<(), VmError> {
+    let &[series, value] = process.get_stack_mut().pop_n()?;
+    let memory = process.memory_mut();
+    let items = Items::new(memory, series, value)?;
+    let offset = memory.item_offset(series)?;
+    items.insert(memory, series, offset)?;
+    let result = series.with_index(offset + items.len())?;
+    process.get_stack_mut().push(result).map_err(Into::into)
+}
+
+/// Inserts a value at the tail of a series, and returns the series at its
+/// head.
+fn append(process: &mut Process) -> Result<(), VmError> {
+    let &[series, value] = process.get_stack_mut().pop_n()?;
+    let memory = process.memory_mut();
+    let items = Items::new(memory, series, value)?;
+    let tail = memory.item_range(series)?.end;
+    items.insert(memory, series, tail)?;
+    let result = memory.head(series)?;
+    process.get_stack_mut().push(result).map_err(Into::into)
+}
+
+fn change(process: &mut Process) -> Result<(), VmError> {
+    let &[series, value] = process.get_stack_mut().pop_n()?;
+    let memory = process.memory_mut();
+    let items = Items::new(memory, series, value)?;
+    let offset = memory.item_offset(series)?;
+    match &items {
+        Items::Values(values) => memory.change(Series::new(series.data()), offset, values)?,
+        Items::Text(text) => {
+            // replace whole characters, as many as the text has
+            let count = text.chars().count() as Offset;
+            let end = offset + char_offset(memory.string_at(series)?, count) as Offset;
+            let string = series.as_any_string()?;
+            memory.remove(string, offset..end)?;
+            memory.insert(string, offset, text.as_bytes())?;
+        }
+        Items::Bytes(bytes) => memory.change(Series::new(series.data()), offset, bytes)?,
+    }
+    let result = series.with_index(offset + items.len())?;
+    process.get_stack_mut().push(result).map_err(Into::into)
+}
+
+fn remove(process: &mut Process) -> Result<(), VmError> {
+    let &[series] = process.get_stack_mut().pop_n()?;
+    let memory = process.memory_mut();
+    let index = memory.item_offset(series)?;
+    if !memory.is_tail(series)? {
+        if series.is_any_string() {
+            // remove a whole character
+            let char_len = memory
+                .string_at(series)?
+                .chars()
+                .next()
+                .map_or(0, char::len_utf8);
+            let range = index..index + char_len as Offset;
+            memory.remove(Series::<u8>::new(series.data()), range)?;
+        } else if series.is_binary() {
+            memory.remove(series.as_binary()?, index..index + 1)?;
+        } else {
+            memory.remove(Series::<Value>::new(series.data()), index..index + 1)?;
+        }
+    }
+    process.get_stack_mut().push(series).map_err(Into::into)
+}
+
+fn copy(process: &mut Process) -> Result<(), VmError> {
+    let &[series] = process.get_stack_mut().pop_n()?;
+    let memory = process.memory_mut();
+    let range = memory.item_range(series)?;
+    let result = match series.kind() {
+        _ if series.is_any_string() => {
+            let copy = memory.copy_part(series.as_any_string()?, range)?;
+            Value::new(series.kind(), copy.address())
+        }
+        Value::BINARY => Value::binary(memory.copy_part(series.as_binary()?, range)?),
+        _ if series.is_any_block() => {
+            let copy = memory.copy_part(Series::<Value>::new(series.data()), range)?;
+            Value::new(series.kind(), copy.address())
+        }
+        _ => return Err(MemoryError::TypeMismatch.into()),
+    };
+    process.get_stack_mut().push(result).map_err(Into::into)
+}
+
+/// Copies at most `count` items of a series, or characters of a string,
+/// from its position.
+fn copy_part(process: &mut Process) -> Result<(), VmError> {
+    let &[series, count] = process.get_stack_mut().pop_n()?;
+    let memory = process.memory_mut();
+    let count = count.as_int()?.clamp(0, memory.length_of(series)? as i64) as Offset;
+    let start = memory.item_range(series)?.start;
+    let range = if series.is_any_string() {
+        start..start + char_offset(memory.string_at(series)?, count) as Offset
+    } else {
+        start..start + count
+    };
+    let result = match series.kind() {
+        _ if series.is_any_string() => {
+            let copy = memory.copy_part(series.as_any_string()?, range)?;
+            Value::new(series.kind(), copy.address())
+        }
+        Value::BINARY => Value::binary(memory.copy_part(series.as_binary()?, range)?),
+        _ if series.is_any_block() => {
+            let copy = memory.copy_part(Series::<Value>::new(series.data()), range)?;
+            Value::new(series.kind(), copy.address())
+        }
+        _ => return Err(MemoryError::TypeMismatch.into()),
+    };
+    process.get_stack_mut().push(result).map_err(Into::into)
+}
+
+fn reverse(process: &mut Process) -> Result<(), VmError> {
+    let &[series] = process.get_stack_mut().pop_n()?;
+    let memory = process.memory_mut();
+    let range = memory.item_range(series)?;
+    if series.is_any_string() {
+        // reverse characters, not bytes
+        let reversed: String = memory.string_at(series)?.chars().rev().collect();
+        memory.change(series.as_any_string()?, range.start, reversed.as_bytes())?;
+    } else if series.is_binary() {
+        memory.reverse(series.as_binary()?, range)?;
+    } else {
+        memory.reverse(Series::<Value>::new(series.data()), range)?;
+    }
+    process.get_stack_mut().push(series).map_err(Into::into)
+}
+
+/// Returns the item of a series or a tuple at a 1-based index from its
+/// position, or none past its tail.
+fn pick(process: &mut Process) -> Result<(), VmError> {
+    let &[series, index] = process.get_stack_mut().pop_n()?;
+    if !index.is_int() {
+        return Err(MemoryError::TypeMismatch.into());
+    }
+    let result = select(process.memory(), series, index)?;
+    process.get_stack_mut().push(result).map_err(Into::into)
+}
+
+fn equal(process: &mut Process) -> Result<(), VmError> {
+    let &[a, b] = process.get_stack_mut().pop_n()?;
+    let result = process.memory().equal(a, b)?;
+    process
+        .get_stack_mut()
+        .push(Value::bool(result))
+        .map_err(Into::into)
+}
+
+fn not_equal(process: &mut Process) -> Result<(), VmError> {
+    let &[a, b] = process.get_stack_mut().pop_n()?;
+    let result = !process.memory().equal(a, b)?;
+    process
+        .get_stack_mut()
+        .push(Value::bool(result))
+        .map_err(Into::into)
+}
+
+/// Sorts a block in place from its position.
+fn sort(process: &mut Process) -> Result<(), VmError> {
+    let &[series] = process.get_stack_mut().pop_n()?;
+    let memory = process.memory_mut();
+    let mut items = memory.items_at(series)?.to_vec();
+    try_sort(&mut items, &mut |a, b| memory.compare(*a, *b))?;
+    memory.change(Series::new(series.data()), series.index(), &items)?;
+    process.get_stack_mut().push(series).map_err(Into::into)
+}
+
+/// Merge sorts `items` stably with a comparison that can fail, stopping at
+/// the first error. `sort_by` cannot stop, and an ordering made up for the
+/// failed comparisons would not be total.
+fn try_sort<T: Copy, E>(
+    items: &mut [T],
+    compare: &mut impl FnMut(&T, &T) -> Result<Ordering, E>,
+) -> Result<(), E> {
+    if items.len() < 2 {
+        return Ok(());
+    }
+    let mid = items.len() / 2;
+    try_sort(&mut items[..mid], compare)?;
+    try_sort(&mut items[mid..], compare)?;
+    let mut merged = Vec::with_capacity(items.len());
+    let (mut i, mut j) = (0, mid);
+    while i < mid && j < items.len() {
+        if compare(&items[j], &items[i])? == Ordering::Less {
+            merged.push(items[j]);
+            j += 1;
+        } else {
+            merged.push(items[i]);
+            i += 1;
+        }
+    }
+    merged.extend_from_slice(&items[i..mid]);
+    merged.extend_from_slice(&items[j..]);
+    items.copy_from_slice(&merged);
+    Ok(())
+}
+
+/// Copies a block from its position, without duplicate items.
+fn unique(process: &mut Process) -> Result<(), VmError> {
+    let &[series] = process.get_stack_mut().pop_n()?;
+    let memory = process.memory_mut();
+    let mut seen = HashMap::<u64, Vec<Value>>::new();
+    let mut items = Vec::new();
+    for &item in memory.items_at(series)? {
+        let mut hasher = DefaultHasher::new();
+        memory.hash_value(item, &mut hasher)?;
+        let bucket = seen.entry(hasher.finish()).or_default();
+        let mut duplicate = false;
+        for other in bucket.iter() {
+            if memory.equal(item, *other)? {
+                duplicate = true;
+                break;
+            }
+        }
+        if !duplicate {
+            bucket.push(item);
+            items.push(item);
+        }
+    }
+    let result = memory.alloc_items(&items)?;
+    let result = Value::new(series.kind(), result.address());
+    process.get_stack_mut().push(result).map_err(Into::into)
+}
+
+/// Copies the bytes of a string or binary from its position into a binary.
+fn to_binary(process: &mut Process) -> Result<(), VmError> {
+    let &[value] = process.get_stack_mut().pop_n()?;
+    let memory = process.memory_mut();
+    let bytes = memory.bytes_at(value)?.to_vec();
+    let result = memory.alloc_items(&bytes).map(Value::binary)?;
+    process.get_stack_mut().push(result).map_err(Into::into)
+}
+
+/// Copies the bytes of a binary or string from its position into a string,
+/// if they are valid UTF-8, or formats a date, a time, money, a tuple, a
+/// pair or a character.
+fn to_string(process: &mut Process) -> Result<(), VmError> {
+    let &[value] = process.get_stack_mut().pop_n()?;
+    let memory = process.memory_mut();
+    let text = match value.kind() {
+        Value::DATE => value.as_date()?.to_string(),
+        Value::TIME => value.as_time()?.to_string(),
+        Value::MONEY => value.as_money()?.to_string(),
+        Value::TUPLE => value.as_tuple()?.to_string(),
+        Value::PAIR => value.as_pair()?.to_string(),
+        Value::CHAR => value.as_char()?.to_string(),
+        _ => {
+            String::from_utf8(memory.bytes_at(value)?.to_vec()).map_err(|_| VmError::InvalidUtf8)?
+        }
+    };
+    let result = memory.alloc_string(&text).map(Value::string)?;
+    process.get_stack_mut().push(result).map_err(Into::into)
+}
+
+/// Width in bytes of an integer in a binary: 1, 2, 4 or 8
+fn int_width(width: Value) -> Result<usize, VmError> {
+    match width.as_int()? {
+        width @ (1 | 2 | 4 | 8) => Ok(width as usize),
+        _ => Err(MemoryError::OutOfBounds.into()),
+    }
+}
+
+/// Reads a big-endian integer of `width` bytes at `offset` from the
+/// position of a binary. Integers narrower than 8 bytes are unsigned.
+fn read_int(process: &mut Process) -> Result<(), VmError> {
+    let &[binary, offset, width] = process.get_stack_mut().pop_n()?;
+    let width = int_width(width)?;
+    binary.as_binary()?;
+    let start = usize::try_from(offset.as_int()?).map_err(|_| MemoryError::OutOfBounds)?;
+    let bytes = process
+        .memory()
+        .bytes_at(binary)?
+        .get(start..start.saturating_add(width))
+        .ok_or(MemoryError::OutOfBounds)?;
+    let result = bytes.iter().fold(0u64, |acc, byte| acc << 8 | *byte as u64);
+    process
+        .get_stack_mut()
+        .push(Value::int(result as i64))
+        .map_err(Into::into)
+}
+
+/// Writes a big-endian integer of `width` bytes at `offset` from the
+/// position of a binary, extending it past its tail as needed. The value
+/// must fit the width, as a signed or an unsigned integer.
+fn write_int(process: &mut Process) -> Result<(), VmError> {
+    let &[binary, offset, width, value] = process.get_stack_mut().pop_n()?;
+    let width = int_width(width)?;
+    let value = value.as_int()?;
+    if width < 8 {
+        let bits = width as u32 * 8;
+        if value < -(1 << (bits - 1)) || value >= 1 << bits {
+            return Err(VmError::IntegerOverflow);
+        }
+    }
+    let offset = Offset::try_from(offset.as_int()?).map_err(|_| MemoryError::OutOfBounds)?;
+    let index = binary
+        .index()
+        .checked_add(offset)
+        .ok_or(MemoryError::OutOfBounds)?;
+    let bytes = &value.to_be_bytes()[8 - width..];
+    process
+        .memory_mut()
+        .change(binary.as_binary()?, index, bytes)?;
+    process.get_stack_mut().push(binary).map_err(Into::into)
+}
+
+/// Seconds of a time, an integer unless they have a fraction
+fn seconds(time: Time) -> Value {
+    let seconds = time.second();
+    if seconds.fract() == 0.0 {
+        Value::int(seconds as i64)
+    } else {
+        Value::float(seconds)
+    }
+}
+
+fn date_field(date: Date, field: &str) -> Result<Value, VmError> {
+    let time = date.time().unwrap_or_default();
+    let result = match field {
+        "year" => Value::int(date.year()),
+        "month" => Value::int(date.month() as i64),
+        "day" => Value::int(date.day() as i64),
+        "weekday" => Value::int(date.weekday() as i64),
+        "yearday" => Value::int(date.yearday() as i64),
+        "date" => Value::date(date.date()),
+        "time" => date.time().map_or(Value::none(), Value::time),
+        "zone" => date.zone().map_or(Value::none(), |zone| {
+            Value::time(Time::from_nanos(zone as i64 * Time::MINUTE))
+        }),
+        "hour" => Value::int(time.hour()),
+        "minute" => Value::int(time.minute()),
+        "second" => seconds(time),
+        _ => return Err(MemoryError::WordNotFound.into()),
+    };
+    Ok(result)
+}
+
+fn time_field(time: Time, field: &str) -> Result<Value, VmError> {
+    match field {
+        "hour" => Ok(Value::int(time.hour())),
+        "minute" => Ok(Value::int(time.minute())),
+        "second" => Ok(seconds(time)),
+        _ => Err(MemoryError::WordNotFound.into()),
+    }
+}
+
+/// Selects the part of `target` named by a path item `key`: a field of a
+/// date or a time (`d/year`, `t/second`), a coordinate of a pair (`p/x`),
+/// the item of a tuple at a 1-based index (`t/1`), the item of a block, the
+/// character of a string or the byte of a binary at a 1-based index from
+/// its position (`b/2`), or the value following a word in a block
+/// (`b/name`). Items past the tail and missing words give none.
+pub(crate) fn select(memory: &Memory, target: Value, key: Value) -> Result<Value, VmError> {
+    match (target.kind(), key.kind()) {
+        (Value::DATE, Value::WORD) => date_field(
+            target.as_date()?,
+            memory.get_string(Series::new(key.data()))?,
+        ),
+        (Value::TIME, Value::WORD) => time_field(
+            target.as_time()?,
+            memory.get_string(Series::new(key.data()))?,
+        ),
+        (Value::PAIR, Value::WORD) => {
+            let pair = target.as_pair()?;
+            match memory.get_string(Series::new(key.data()))? {
+                "x" => Ok(Value::int(pair.x() as i64)),
+                "y" => Ok(Value::int(pair.y() as i64)),
+                _ => Err(MemoryError::WordNotFound.into()),
+            }
+        }
+        (Value::TUPLE, Value::INT) => {
+            let tuple = target.as_tuple()?;
+            let item = usize::try_from(key.as_int()?)
+                .ok()
+                .and_then(|index| tuple.items().get(index.checked_sub(1)?));
+            Ok(item.map_or(Value::none(), |item| Value::int(*item as i64)))
+        }
+        (_, Value::INT) if target.is_any_string() => {
+            let char = usize::try_from(key.as_int()?).ok().and_then(|index| {
+                memory
+                    .string_at(target)
+                    .ok()?
+                    .chars()
+                    .nth(index.checked_sub(1)?)
+            });
+            Ok(char.map_or(Value::none(), Value::char))
+        }
+        (Value::BINARY, Value::INT) => {
+            let bytes = memory.bytes_at(target)?;
+            let byte = usize::try_from(key.as_int()?)
+                .ok()
+                .and_then(|index| bytes.get(index.checked_sub(1)?));
+            Ok(byte.map_or(Value::none(), |byte| Value::int(*byte as i64)))
+        }
+        (_, Value::INT) if target.is_any_block() => {
+            let items = memory.items_at(target)?;
+            let item = usize::try_from(key.as_int()?)
+                .ok()
+                .and_then(|index| items.get(index.checked_sub(1)?));
+            Ok(item.copied().unwrap_or(Value::none()))
+        }
+        (_, Value::WORD) if target.is_any_block() => {
+            let items = memory.items_at(target)?;
+            let item = key_position(items, key).and_then(|at| items.get(at + 1));
+            Ok(item.copied().unwrap_or(Value::none()))
+        }
+        _ => Err(MemoryError::TypeMismatch.into()),
+    }
+}
+
+/// Position of the first word of any kind with the symbol of `key`
+fn key_position(items: &[Value], key: Value) -> Option<usize> {
+    items
+        .iter()
+        .position(|item| item.is_any_word() && item.data() == key.data())
+}
+
+/// 0-based offset of a 1-based index `key` into `len` items
+fn index_offset(key: Value, len: Offset) -> Result<Offset, MemoryError> {
+    Offset::try_from(key.as_int()?)
+        .ok()
+        .and_then(|index| index.checked_sub(1))
+        .filter(|offset| *offset < len)
+        .ok_or(MemoryError::OutOfBounds)
+}
+
+/// Changes the part of `target` named by a path item `key` to `value`, the
+/// counterpart of [`select`] for set-paths: the item of a block, the
+/// character of a string or the byte of a binary at a 1-based index from
+/// its position (`b/2: x`), or the value following a word in a block
+/// (`b/name: x`). A missing word is appended to the block with the value.
+pub(crate) fn assign(
+    memory: &mut Memory,
+    target: Value,
+    key: Value,
+    value: Value,
+) -> Result<(), VmError> {
+    match (target.kind(), key.kind()) {
+        (_, Value::INT) if target.is_any_block() => {
+            let offset = index_offset(key, memory.length_of(target)?)?;
+            let series = Series::<Value>::new(target.data());
+            memory.change(series, target.index() + offset, &[value])?;
+        }
+        (_, Value::WORD) if target.is_any_block() => {
+            let items = memory.items_at(target)?;
+            let (at, len) = (key_position(items, key), items.len());
+            let series = Series::<Value>::new(target.data());
+            match at {
+                Some(at) if at + 1 < len => {
+                    let index = target.index() + at as Offset + 1;
+                    memory.change(series, index, &[value])?;
+                }
+                Some(_) => {
+                    let tail = memory.tail(target)?.index();
+                    memory.insert(series, tail, &[value])?;
+                }
+                None => {
+                    let tail = memory.tail(target)?.index();
+                    memory.insert(series, tail, &[key, value])?;
+                }
+            }
+        }
+        (_, Value::INT) if target.is_any_string() => {
+            let char = value.as_char()?;
+            let string = memory.string_at(target)?;
+            let offset = index_offset(key, string.chars().count() as Offset)?;
+            let (at, old) = string
+                .char_indices()
+                .nth(offset as usize)
+                .ok_or(MemoryError::OutOfBounds)?;
+            let start = memory.item_offset(target)? + at as Offset;
+            let series = target.as_any_string()?;
+            memory.remove(series, start..start + old.len_utf8() as Offset)?;
+            memory.insert(series, start, char.encode_utf8(&mut [0; 4]).as_bytes())?;
+        }
+        (Value::BINARY, Value::INT) => {
+            let offset = index_offset(key, memory.length_of(target)?)?;
+            let byte = u8::try_from(value.as_int()?).map_err(|_| MemoryError::OutOfBounds)?;
+            memory.change(target.as_binary()?, target.index() + offset, &[byte])?;
+        }
+        _ => return Err(MemoryError::TypeMismatch.into()),
+    }
+    Ok(())
+}
+
 /// Native Function of The Standard Library for the Rebel VM.
 pub const NATIVES: &[NativeDescriptor] = &[
     NativeDescriptor::new("add", "add two numbers function", add, 2),
@@ -60,4 +880,49 @@ pub const NATIVES: &[NativeDescriptor] = &[
     NativeDescriptor::new_op("<", "less than operator", lt, 1, 2),
     NativeDescriptor::new("either", "execute one of two blocks", either, 3),
     NativeDescriptor::new("func", "create a function", func, 2),
+    NativeDescriptor::new("compose", "copy a block evaluating its parens", compose, 1),
+    NativeDescriptor::new("recycle", "run the garbage collector", recycle, 0),
+    NativeDescriptor::new("head", "series at its head", head, 1),
+    NativeDescriptor::new("tail", "series past its last item", tail, 1),
+    NativeDescriptor::new("next", "series at the next position", next, 1),
+    NativeDescriptor::new("back", "series at the previous position", back, 1),
+    NativeDescriptor::new("skip", "series moved by an offset", skip, 2),
+    NativeDescriptor::new("at", "series at a 1-based index", at, 2),
+    NativeDescriptor::new("head?", "true if series is at its head", is_head, 1),
+    NativeDescriptor::new("tail?", "true if series is at its tail", is_tail, 1),
+    NativeDescriptor::new("index?", "1-based position of series", index_of, 1),
+    NativeDescriptor::new("length?", "items from the position of series", length_of, 1),
+    NativeDescriptor::new("insert", "insert a value into a series", insert, 2),
+    NativeDescriptor::new(
+        "append",
+        "insert a value at the tail of a series",
+        append,
+        2,
+    ),
+    NativeDescriptor::new("change", "overwrite the items of a series", change, 2),
+    NativeDescriptor::new("remove", "remove an item from a series", remove, 1),
+    NativeDescriptor::new("copy", "copy a series from its position", copy, 1),
+    NativeDescriptor::new("reverse", "reverse a series from its position", reverse, 1),
+    NativeDescriptor::new("pick", "item of a series at a 1-based index", pick, 2),
+    NativeDescriptor::new("equal?", "true if values are equal", equal, 2),
+    NativeDescriptor::new_op("=", "equal operator", equal, 1, 2),
+    NativeDescriptor::new_op("<>", "not equal operator", not_equal, 1, 2),
+    NativeDescriptor::new("sort", "sort a block from its position", sort, 1),
+    NativeDescriptor::new("unique", "copy a block without duplicates", unique, 1),
+    NativeDescriptor::new("subtract", "subtract two numbers function", subtract, 2),
+    NativeDescriptor::new_op("-", "subtract two numbers operator", subtract, 1, 2),
+    NativeDescriptor::new("multiply", "multiply two numbers function", multiply, 2),
+    NativeDescriptor::new_op("*", "multiply two numbers operator", multiply, 1, 2),
+    NativeDescriptor::new("divide", "divide two numbers function", divide, 2),
+    NativeDescriptor::new(
+        "to-money",
+        "convert a number or string to money",
+        to_money,
+        1,
+    ),
+    NativeDescriptor::new("copy-part", "copy part of a series", copy_part, 2),
+    NativeDescriptor::new("to-binary", "convert a string to binary", to_binary, 1),
+    NativeDescriptor::new("to-string", "convert a value to string", to_string, 1),
+    NativeDescriptor::new("read-int", "read an integer from a binary", read_int, 3),
+    NativeDescriptor::new("write-int", "write an integer into a binary", write_int, 4),
 ];
@@ -0,0 +1,273 @@
+// Rebel™ © 2025 Huly Labs • https://hulylabs.com • SPDX-License-Identifier: MIT
+
+//! Tuples and pairs
+//!
+//! A [`Tuple`] is a short sequence of bytes written with dots, like a
+//! version `1.2.3` or an address `192.168.0.1`. A [`Pair`] is two integers
+//! written with an `x`, like a size `640x480` or a point `-10x20`. Both fit
+//! the 64 bits of a value: a tuple as its length followed by up to 7
+//! bytes, a pair as two i32.
+
+use std::fmt;
+use std::str::FromStr;
+use thiserror::Error;
+
+#[derive(Debug, Error, PartialEq)]
+pub enum TupleError {
+    #[error("invalid tuple or pair")]
+    Invalid,
+    #[error("tuple or pair item out of range")]
+    OutOfRange,
+}
+
+/// A sequence of 3 to 7 bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Tuple {
+    len: u8,
+    items: [u8; Tuple::MAX_LEN],
+}
+
+impl Tuple {
+    pub const MIN_LEN: usize = 3;
+    pub const MAX_LEN: usize = 7;
+
+    pub fn new(items: &[u8]) -> Result<Self, TupleError> {
+        if !(Self::MIN_LEN..=Self::MAX_LEN).contains(&items.len()) {
+            return Err(TupleError::OutOfRange);
+        }
+        let mut tuple = Self {
+            len: items.len() as u8,
+            items: [0; Self::MAX_LEN],
+        };
+        tuple.items[..items.len()].copy_from_slice(items);
+        Ok(tuple)
+    }
+
+    pub fn items(&self) -> &[u8] {
+        &self.items[..self.len as usize]
+    }
+
+    /// Returns the items without trailing zeros, which do not make tuples
+    /// differ: `1.2.0` equals `1.2.0.0`.
+    pub fn significant(&self) -> &[u8] {
+        let len = self.items().iter().rposition(|item| *item != 0);
+        &self.items[..len.map_or(0, |at| at + 1)]
+    }
+
+    /// Applies `f` to every item, clamping the results to bytes
+    pub fn map(self, f: impl Fn(f64) -> f64) -> Self {
+        let mut result = self;
+        for item in &mut result.items[..self.len as usize] {
+            *item = f(*item as f64) as u8;
+        }
+        result
+    }
+
+    /// Applies `f` to the items of both tuples pairwise, clamping the
+    /// results to bytes. The shorter tuple is padded with zeros.
+    pub fn zip(self, other: Tuple, f: impl Fn(f64, f64) -> f64) -> Self {
+        let mut result = Self {
+            len: self.len.max(other.len),
+            ..self
+        };
+        for (i, item) in result.items[..result.len as usize].iter_mut().enumerate() {
+            *item = f(self.items[i] as f64, other.items[i] as f64) as u8;
+        }
+        result
+    }
+
+    pub fn to_bits(&self) -> u64 {
+        let mut bytes = [0; 8];
+        bytes[0] = self.len;
+        bytes[1..].copy_from_slice(&self.items);
+        u64::from_le_bytes(bytes)
+    }
+
+    pub fn from_bits(bits: u64) -> Self {
+        let bytes = bits.to_le_bytes();
+        let mut items = [0; Self::MAX_LEN];
+        items.copy_from_slice(&bytes[1..]);
+        Self {
+            len: bytes[0],
+            items,
+        }
+    }
+}
+
+/// Parses 3 to 7 decimal bytes separated by dots, like `192.168.0.1`.
+impl FromStr for Tuple {
+    type Err = TupleError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut items = Vec::new();
+        for item in s.split('.') {
+            if item.is_empty() || item.len() > 3 || !item.bytes().all(|b| b.is_ascii_digit()) {
+                return Err(TupleError::Invalid);
+            }
+            let item = item.parse::<u16>().map_err(|_| TupleError::Invalid)?;
+            items.push(u8::try_from(item).map_err(|_| TupleError::OutOfRange)?);
+        }
+        Self::new(&items)
+    }
+}
+
+impl fmt::Display for Tuple {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, item) in self.items().iter().enumerate() {
+            if i > 0 {
+                f.write_str(".")?;
+            }
+            write!(f, "{item}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Two integers, like a size or a point.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Pair {
+    x: i32,
+    y: i32,
+}
+
+impl Pair {
+    pub fn new(x: i32, y: i32) -> Self {
+        Self { x, y }
+    }
+
+    /// Returns a pair with `value` twice, for arithmetic with integers
+    pub fn splat(value: i64) -> Option<Self> {
+        let value = i32::try_from(value).ok()?;
+        Some(Self::new(value, value))
+    }
+
+    pub fn x(&self) -> i32 {
+        self.x
+    }
+
+    pub fn y(&self) -> i32 {
+        self.y
+    }
+
+    pub fn checked_add(self, other: Pair) -> Option<Self> {
+        Some(Self::new(
+            self.x.checked_add(other.x)?,
+            self.y.checked_add(other.y)?,
+        ))
+    }
+
+    pub fn checked_sub(self, other: Pair) -> Option<Self> {
+        Some(Self::new(
+            self.x.checked_sub(other.x)?,
+            self.y.checked_sub(other.y)?,
+        ))
+    }
+
+    pub fn checked_mul(self, other: Pair) -> Option<Self> {
+        Some(Self::new(
+            self.x.checked_mul(other.x)?,
+            self.y.checked_mul(other.y)?,
+        ))
+    }
+
+    /// Divides each coordinate, truncating; None if one is divided by 0
+    pub fn checked_div(self, other: Pair) -> Option<Self> {
+        Some(Self::new(
+            self.x.checked_div(other.x)?,
+            self.y.checked_div(other.y)?,
+        ))
+    }
+
+    pub fn to_bits(&self) -> u64 {
+        (self.y as u32 as u64) << 32 | self.x as u32 as u64
+    }
+
+    pub fn from_bits(bits: u64) -> Self {
+        Self::new(bits as u32 as i32, (bits >> 32) as u32 as i32)
+    }
+}
+
+/// Parses two integers separated by `x`, like `640x480` or `-10x-20`.
+impl FromStr for Pair {
+    type Err = TupleError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (x, y) = s.split_once(['x', 'X']).ok_or(TupleError::Invalid)?;
+        let coordinate = |s: &str| {
+            let digits = s.strip_prefix(['+', '-']).unwrap_or(s);
+            if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+                return Err(TupleError::Invalid);
+            }
+            s.parse::<i32>().map_err(|_| TupleError::OutOfRange)
+        };
+        Ok(Self::new(coordinate(x)?, coordinate(y)?))
+    }
+}
+
+impl fmt::Display for Pair {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}x{}", self.x, self.y)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tuple(s: &str) -> Tuple {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_tuple() {
+        assert_eq!(tuple("192.168.0.1").items(), [192, 168, 0, 1]);
+        assert_eq!(tuple("1.2.3").to_string(), "1.2.3");
+        assert_eq!(tuple("1.2.0.0").significant(), [1, 2]);
+        for invalid in ["1..2", "1.2.", ".1.2", "1.2.a", "1.2.1234", "-1.2.3"] {
+            assert_eq!(
+                invalid.parse::<Tuple>(),
+                Err(TupleError::Invalid),
+                "{invalid}"
+            );
+        }
+        for invalid in ["1.2.256", "1.2", "1.2.3.4.5.6.7.8"] {
+            assert_eq!(
+                invalid.parse::<Tuple>(),
+                Err(TupleError::OutOfRange),
+                "{invalid}"
+            );
+        }
+
+        let t = tuple("1.2.3.4.5.6.255");
+        assert_eq!(Tuple::from_bits(t.to_bits()), t);
+        assert_eq!(tuple("1.2.250").map(|x| x + 10.0), tuple("11.12.255"));
+        assert_eq!(tuple("1.2.3").map(|x| x - 2.0), tuple("0.0.1"));
+        let sum = tuple("1.2.3").zip(tuple("10.20.30.40"), |a, b| a + b);
+        assert_eq!(sum, tuple("11.22.33.40"));
+    }
+
+    #[test]
+    fn test_pair() {
+        let pair: Pair = "640x480".parse().unwrap();
+        assert_eq!((pair.x(), pair.y()), (640, 480));
+        assert_eq!("-10X+20".parse(), Ok(Pair::new(-10, 20)));
+        assert_eq!(Pair::new(-10, 20).to_string(), "-10x20");
+        for invalid in ["10", "x20", "10x", "10x2x0", "1.5x2"] {
+            assert_eq!(
+                invalid.parse::<Pair>(),
+                Err(TupleError::Invalid),
+                "{invalid}"
+            );
+        }
+        assert_eq!("1x99999999999".parse::<Pair>(), Err(TupleError::OutOfRange));
+
+        let pair = Pair::new(i32::MIN, -1);
+        assert_eq!(Pair::from_bits(pair.to_bits()), pair);
+        assert_eq!(pair.checked_div(Pair::new(1, 0)), None);
+        assert_eq!(
+            Pair::new(7, -7).checked_div(Pair::splat(2).unwrap()),
+            Some(Pair::new(3, -3))
+        );
+        assert_eq!(pair.checked_sub(Pair::new(1, 0)), None);
+    }
+}
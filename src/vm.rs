@@ -1,11 +1,19 @@
 // Rebel™ © 2025 Huly Labs • https://hulylabs.com • SPDX-License-Identifier: MIT
 
+use std::collections::HashMap;
 use std::mem::zeroed;
+use std::ops::Range;
 
+use crate::date::{Date, DateError, Time};
+use crate::gc::Roots;
 use crate::mem::{
-    Address, Block, Func, Memory, MemoryError, NativeFunc, Series, Short, Type, Value, Word,
+    Address, Block, Func, Memory, MemoryError, NativeFunc, Offset, Owner, Series, Short, Type,
+    Value, Word,
 };
-use crate::parse::{Collector, Parser, ParserError, WordKind};
+use crate::money::{Money, MoneyError, Rounding};
+use crate::parse::{Collector, Parser, ParserError, PathKind, WordKind};
+use crate::stdlib::{assign, select};
+use crate::tuple::{Pair, Tuple};
 use thiserror::Error;
 
 #[derive(Debug, Error)]
@@ -13,13 +21,45 @@ pub enum VmError {
     #[error(transparent)]
     ParserError(#[from] ParserError<MemoryError>),
     #[error(transparent)]
-    MemoryError(#[from] MemoryError),
+    MemoryError(MemoryError),
+    #[error("Process memory quota exceeded")]
+    QuotaExceeded,
     #[error("Invalid code")]
     InvalidCode,
     #[error("Integer overflow")]
     IntegerOverflow,
+    #[error(transparent)]
+    MoneyError(MoneyError),
+    #[error("Division by zero")]
+    DivisionByZero,
+    #[error("Invalid UTF-8")]
+    InvalidUtf8,
+    #[error(transparent)]
+    DateError(#[from] DateError),
     #[error("bad native function index")]
     BadNativeFunctionIndex,
+    #[error("function arity unknown at compile time")]
+    UnknownArity,
+    #[error("too many processes")]
+    TooManyProcesses,
+}
+
+impl From<MemoryError> for VmError {
+    fn from(error: MemoryError) -> Self {
+        match error {
+            MemoryError::QuotaExceeded => VmError::QuotaExceeded,
+            error => VmError::MemoryError(error),
+        }
+    }
+}
+
+impl From<MoneyError> for VmError {
+    fn from(error: MoneyError) -> Self {
+        match error {
+            MoneyError::DivisionByZero => VmError::DivisionByZero,
+            error => VmError::MoneyError(error),
+        }
+    }
 }
 
 //
@@ -29,14 +69,27 @@ type Op = u8;
 pub struct Code;
 
 impl Code {
-    const RET: Op = 0;
-    const CONST: Op = 1;
-    const NONE: Op = 2;
-    const WORD: Op = 3;
-    const SET_WORD: Op = 4;
-    const LEAVE: Op = 5;
-    const CALL_NATIVE: Op = 6;
-    const CALL_FUNC: Op = 7;
+    pub const RET: Op = 0;
+    pub const CONST: Op = 1;
+    pub const NONE: Op = 2;
+    pub const WORD: Op = 3;
+    pub const SET_WORD: Op = 4;
+    pub const LEAVE: Op = 5;
+    pub const CALL_NATIVE: Op = 6;
+    pub const CALL_FUNC: Op = 7;
+    /// Like `CONST`, with the whole type word and both data words, for
+    /// series values past their head and 64-bit numbers
+    pub const CONST_VALUE: Op = 8;
+    /// Replaces the value on top of the stack with its part named by the
+    /// key that follows, a whole value like in `CONST_VALUE`
+    pub const SELECT: Op = 9;
+    /// Like `SELECT`, with the key on top of the stack, above the value
+    pub const SELECT_KEY: Op = 10;
+    /// Like `WORD`, without calling a function
+    pub const GET_WORD: Op = 11;
+    /// Assigns the value on top of the stack to the part of the value below
+    /// the key below it, and leaves the value
+    pub const SET_PATH: Op = 12;
 }
 
 //
@@ -44,8 +97,9 @@ impl Code {
 #[derive(Debug, Clone, Copy)]
 enum Call {
     SetWord(Address),
-    CallNative(Short),
-    CallFunc(Address),
+    SetPath,
+    Native(Short),
+    Func(Address),
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -115,24 +169,58 @@ impl NativeDescriptor {
 pub struct Vm {
     memory: Memory,
     natives: Vec<NativeFn>,
+    /// Owner the next `Process` accounts its allocations to
+    next_owner: Owner,
 }
 
 impl Vm {
+    /// Creates a VM over `memory`, attaching the standard library natives.
+    ///
+    /// Natives already registered in `memory` (e.g. one loaded from an
+    /// image) are matched by name and keep their ids, so compiled code stays
+    /// valid; new natives are registered and bound to their words.
     pub fn new(memory: Memory) -> Result<Self, MemoryError> {
         let descs = crate::stdlib::NATIVES;
         let mut vm = Self {
             memory,
             natives: Vec::<NativeFn>::with_capacity(descs.len()),
+            next_owner: 1,
         };
-        let natives = vm.memory.alloc::<NativeFunc>(descs.len())?;
+        let natives = match vm.memory.natives()? {
+            Some(natives) => natives,
+            None => {
+                let natives = vm.memory.alloc::<Address>(descs.len())?;
+                vm.memory.set_natives(natives)?;
+                natives
+            }
+        };
+
+        for address in vm.memory.get_items(natives)?.to_vec() {
+            let native = vm.memory.get::<NativeFunc>(address)?;
+            let name = vm.memory.get_string(native.name())?;
+            let desc = descs
+                .iter()
+                .find(|desc| desc.name == name)
+                .ok_or(MemoryError::WordNotFound)?;
+            vm.natives.push(desc.func);
+        }
 
         for native in descs {
             let symbol = vm.memory.get_or_add_symbol(native.name)?;
+            let registered = vm.memory.get_items(natives)?.iter().any(|address| {
+                vm.memory
+                    .get::<NativeFunc>(*address)
+                    .is_ok_and(|func| func.name().address() == symbol.address())
+            });
+            if registered {
+                continue;
+            }
             let description = vm.memory.alloc_string(native.description)?;
             let id = vm.natives.len();
             vm.natives.push(native.func);
-            let native = NativeFunc::new(id, native.arity, native.consume, description);
-            let address = vm.memory.push(natives, native)?;
+            let native = NativeFunc::new(id, native.arity, native.consume, symbol, description);
+            let address = vm.memory.alloc_struct(native)?;
+            vm.memory.push(natives, address)?;
             vm.memory
                 .set_word(symbol.address(), Value::native(address))?;
         }
@@ -140,11 +228,45 @@ impl Vm {
         Ok(vm)
     }
 
+    /// Loads a memory image written by `save_image` and re-attaches the
+    /// native functions by name.
+    pub fn load_image(path: impl AsRef<std::path::Path>) -> Result<Self, MemoryError> {
+        Self::new(Memory::load_image(path)?)
+    }
+
+    pub fn save_image(&self, path: impl AsRef<std::path::Path>) -> Result<(), MemoryError> {
+        self.memory.save_image(path)
+    }
+
     pub fn parse_block(&mut self, input: &str) -> Result<Value, VmError> {
         let mut collector = ParseCollector::new(&mut self.memory);
         Parser::parse_block(input, &mut collector)?;
         collector.stack.pop().map_err(Into::into)
     }
+
+    /// Parses like `parse_block`, and also returns where every parsed item
+    /// comes from in `input`, for reporting errors against the source.
+    pub fn parse_block_with_spans(&mut self, input: &str) -> Result<(Value, SourceMap), VmError> {
+        let mut collector = ParseCollector::new(&mut self.memory);
+        collector.source_map = Some(SourceMap::default());
+        Parser::parse_block(input, &mut collector)?;
+        let block = collector.stack.pop()?;
+        Ok((block, collector.source_map.unwrap_or_default()))
+    }
+}
+
+/// Byte ranges in the source of the items of parsed blocks, parens and
+/// paths. The map is only valid as long as the parsed series are alive.
+#[derive(Debug, Default)]
+pub struct SourceMap {
+    spans: HashMap<(Address, Offset), Range<usize>>,
+}
+
+impl SourceMap {
+    /// Returns where the item at `index` of `block` was parsed from
+    pub fn span(&self, block: Series<Value>, index: Offset) -> Option<Range<usize>> {
+        self.spans.get(&(block.address(), index)).cloned()
+    }
 }
 
 //
@@ -254,6 +376,19 @@ where
 
 pub type ByteCode = ArrayStack<u8, 1024>;
 
+/// Appends the code pushing a constant value
+fn compile_const(code_stack: &mut ByteCode, value: Value) -> Result<(), MemoryError> {
+    if value.index() != 0 || value.2 != 0 {
+        code_stack.push(Code::CONST_VALUE)?;
+        code_stack.extend(&u32::to_le_bytes(value.0))?;
+        code_stack.extend(&u32::to_le_bytes(value.1))?;
+        code_stack.extend(&u32::to_le_bytes(value.2))
+    } else {
+        code_stack.extend(&[Code::CONST, value.kind() as u8])?;
+        code_stack.extend(&u32::to_le_bytes(value.data()))
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 struct InstructionPointer(usize);
 
@@ -275,13 +410,13 @@ impl InstructionPointer {
     }
 
     fn read_u16(&mut self, memory: &Memory) -> Result<u16, MemoryError> {
-        let result = memory.get_u16_ne(self.0).ok_or(MemoryError::OutOfBounds)?;
+        let result = memory.get_u16_le(self.0).ok_or(MemoryError::OutOfBounds)?;
         self.0 += 2;
         Ok(result)
     }
 
     fn read_u32(&mut self, memory: &Memory) -> Result<u32, MemoryError> {
-        let result = memory.get_u32_ne(self.0).ok_or(MemoryError::OutOfBounds)?;
+        let result = memory.get_u32_le(self.0).ok_or(MemoryError::OutOfBounds)?;
         self.0 += 4;
         Ok(result)
     }
@@ -291,6 +426,16 @@ impl InstructionPointer {
     }
 }
 
+/// Return record pushed on every call. `func` is the address of the called
+/// `Func` (0 for plain blocks), whose parameters must be restored on return,
+/// and `context` is the caller's context.
+#[derive(Debug, Clone, Copy)]
+struct Frame {
+    ip: InstructionPointer,
+    func: Address,
+    context: Address,
+}
+
 //
 
 type Stack = ArrayStack<Value, 64>;
@@ -299,17 +444,62 @@ pub struct Process<'a> {
     vm: &'a mut Vm,
     ip: InstructionPointer,
     stack: Stack,
-    call_stack: ArrayStack<InstructionPointer, 64>,
+    call_stack: ArrayStack<Frame, 64>,
+    saved: ArrayStack<Value, 256>,
+    context: Address,
+    owner: Owner,
+    previous_owner: Owner,
+    rounding: Rounding,
 }
 
 impl<'a> Process<'a> {
-    pub fn new(vm: &'a mut Vm) -> Self {
-        Self {
+    /// Creates a process over `vm`. Everything allocated while the process
+    /// exists, including through `memory_mut`, is accounted to it. Fails
+    /// once a `Vm` has run out of owners to tell its processes apart.
+    pub fn new(vm: &'a mut Vm) -> Result<Self, VmError> {
+        let owner = vm.next_owner;
+        vm.next_owner = owner.checked_add(1).ok_or(VmError::TooManyProcesses)?;
+        let previous_owner = vm.memory.set_owner(owner);
+        Ok(Self {
             vm,
             stack: ArrayStack::new(),
             ip: InstructionPointer(0),
             call_stack: ArrayStack::new(),
-        }
+            saved: ArrayStack::new(),
+            context: 0,
+            owner,
+            previous_owner,
+            rounding: Rounding::default(),
+        })
+    }
+
+    /// Creates a process which may hold at most `quota` bytes of heap;
+    /// allocating more fails with `VmError::QuotaExceeded`.
+    pub fn with_quota(vm: &'a mut Vm, quota: Offset) -> Result<Self, VmError> {
+        let process = Self::new(vm)?;
+        process.vm.memory.set_quota(process.owner, Some(quota));
+        Ok(process)
+    }
+
+    /// Returns the bytes of heap currently held by allocations of this
+    /// process.
+    pub fn allocated(&self) -> Offset {
+        self.vm.memory.allocated(self.owner)
+    }
+
+    /// Returns how money natives round their results, half to even unless
+    /// set otherwise.
+    pub fn rounding(&self) -> Rounding {
+        self.rounding
+    }
+
+    pub fn set_rounding(&mut self, rounding: Rounding) {
+        self.rounding = rounding;
+    }
+
+    /// Returns the context words are currently bound in, 0 for system words.
+    pub fn context(&self) -> Address {
+        self.context
     }
 
     pub fn get_stack_mut(&mut self) -> &mut Stack {
@@ -324,12 +514,132 @@ impl<'a> Process<'a> {
         &mut self.vm.memory
     }
 
+    /// Returns the arity of the function being defined when the set-word at
+    /// `ip` is followed by `func [spec]`, so that calls to the word later in
+    /// the same block can be compiled before the function value exists.
+    /// `ip` and `end` are addresses in the resolved items of the block.
+    fn func_definition_arity(
+        &mut self,
+        ip: Address,
+        end: Address,
+    ) -> Result<Option<u8>, MemoryError> {
+        let spec_ip = ip + 2 * Value::SIZE;
+        if spec_ip >= end {
+            return Ok(None);
+        }
+        let func_symbol = self.vm.memory.get_or_add_symbol("func")?.address();
+        let next = self.vm.memory.get::<Value>(ip + Value::SIZE).copied()?;
+        let spec = self.vm.memory.get::<Value>(spec_ip).copied()?;
+        if next == Value::new(Value::WORD, func_symbol) && spec.is_block() {
+            let arity = self.vm.memory.len(spec.as_block()?)?;
+            Ok(Some(arity as u8))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Returns the arity of the function a word is bound to, either from the
+    /// function value it holds or from a definition compiled earlier.
+    fn known_arity(
+        &self,
+        binding: Address,
+        defined_funcs: &ArrayStack<(Address, u8), 64>,
+    ) -> Result<Option<u8>, MemoryError> {
+        let resolved = self.vm.memory.get::<Value>(binding).copied()?;
+        if resolved.is_func() {
+            let func = self.vm.memory.get::<Func>(resolved.data())?;
+            return Ok(Some(func.arity()));
+        }
+        Ok(defined_funcs
+            .as_slice()?
+            .iter()
+            .find(|(address, _)| *address == binding)
+            .map(|(_, arity)| *arity))
+    }
+
+    /// Returns the arity of the function assigned when the set-word at `ip`
+    /// is followed by a get-word of a function, as in `g: :f`.
+    fn func_alias_arity(
+        &mut self,
+        ip: Address,
+        end: Address,
+        defined_funcs: &ArrayStack<(Address, u8), 64>,
+    ) -> Result<Option<u8>, MemoryError> {
+        let next_ip = ip + Value::SIZE;
+        if next_ip >= end {
+            return Ok(None);
+        }
+        let next = self.vm.memory.get::<Value>(next_ip).copied()?;
+        if !next.is_type(Value::GET_WORD) {
+            return Ok(None);
+        }
+        let binding = self
+            .vm
+            .memory
+            .bind_word_in(self.context, next.data(), false)?;
+        self.known_arity(binding, defined_funcs)
+    }
+
+    /// Returns true for a path starting with a word, which is compiled to
+    /// select into the value of the word rather than kept as a constant.
+    fn is_word_path(&self, path: Value) -> Result<bool, MemoryError> {
+        let items = self.vm.memory.items_at(path)?;
+        Ok(items.first().is_some_and(Value::is_word))
+    }
+
+    /// Appends the code fetching the first word of a path and selecting its
+    /// items up to `end`. A get-path fetches the word without calling it.
+    fn compile_path(
+        &mut self,
+        path: Value,
+        end: usize,
+        code_stack: &mut ByteCode,
+        defined_funcs: &mut ArrayStack<(Address, u8), 64>,
+    ) -> Result<(), MemoryError> {
+        let items = self.vm.memory.items_at(path)?.to_vec();
+        let symbol = items[0].data();
+        let binding = self.vm.memory.bind_word_in(self.context, symbol, false)?;
+        if path.is_type(Value::GET_PATH) {
+            code_stack.push(Code::GET_WORD)?;
+        } else {
+            code_stack.push(Code::WORD)?;
+        }
+        code_stack.extend(&u32::to_le_bytes(binding))?;
+        for key in &items[1..end] {
+            if key.is_paren() {
+                self.compile_into(key.as_paren()?, code_stack, defined_funcs)?;
+                code_stack.push(Code::SELECT_KEY)?;
+            } else {
+                code_stack.push(Code::SELECT)?;
+                code_stack.extend(&u32::to_le_bytes(key.0))?;
+                code_stack.extend(&u32::to_le_bytes(key.1))?;
+                code_stack.extend(&u32::to_le_bytes(key.2))?;
+            }
+        }
+        Ok(())
+    }
+
     pub fn compile(&mut self, block: Series<Value>) -> Result<Series<u8>, MemoryError> {
-        let mut defer_stack = ArrayStack::<Defer, 64>::new();
         let mut code_stack = ByteCode::new();
+        let mut defined_funcs = ArrayStack::<(Address, u8), 64>::new();
+        self.compile_into(block, &mut code_stack, &mut defined_funcs)?;
+        code_stack.push(Code::RET)?;
+        self.vm.memory.alloc_items(code_stack.as_slice()?)
+    }
 
+    /// Appends the code of a block to `code_stack`, leaving the value of its
+    /// last expression on the stack. Parens are compiled inline this way.
+    fn compile_into(
+        &mut self,
+        block: Series<Value>,
+        code_stack: &mut ByteCode,
+        defined_funcs: &mut ArrayStack<(Address, u8), 64>,
+    ) -> Result<(), MemoryError> {
+        let mut defer_stack = ArrayStack::<Defer, 64>::new();
+
+        // a block that has grown is read from where its items moved to
         let len = self.vm.memory.len(block)?;
-        let mut ip = block.address() + Block::SIZE;
+        let mut ip = self.vm.memory.resolve(block.address())? + Block::SIZE;
         let end = ip + len * Value::SIZE;
         let mut stack_len = 0;
 
@@ -342,15 +652,16 @@ impl<'a> Process<'a> {
                     match defer.call {
                         Call::SetWord(binding) => {
                             code_stack.push(Code::SET_WORD)?;
-                            code_stack.extend(&u32::to_ne_bytes(binding))?;
+                            code_stack.extend(&u32::to_le_bytes(binding))?;
                         }
-                        Call::CallNative(func_id) => {
+                        Call::SetPath => code_stack.push(Code::SET_PATH)?,
+                        Call::Native(func_id) => {
                             code_stack.push(Code::CALL_NATIVE)?;
-                            code_stack.extend(&u16::to_ne_bytes(func_id))?;
+                            code_stack.extend(&u16::to_le_bytes(func_id))?;
                         }
-                        Call::CallFunc(func_address) => {
+                        Call::Func(slot) => {
                             code_stack.push(Code::CALL_FUNC)?;
-                            code_stack.extend(&u32::to_ne_bytes(func_address))?;
+                            code_stack.extend(&u32::to_le_bytes(slot))?;
                         }
                     }
                     defer_stack.drop()?;
@@ -366,7 +677,7 @@ impl<'a> Process<'a> {
             let value = {
                 let value = self.vm.memory.get::<Value>(ip).copied()?;
                 if value.kind() == Value::WORD {
-                    let resolved = self.vm.memory.get_word(value.data())?;
+                    let resolved = self.vm.memory.get_word_in(self.context, value.data())?;
                     if resolved.kind() == Value::NATIVE_FUNC {
                         resolved
                     } else {
@@ -380,14 +691,26 @@ impl<'a> Process<'a> {
             match value.kind() {
                 Value::WORD => {
                     let symbol = value.data();
-                    let binding = self.vm.memory.bind_word(symbol, false)?;
-                    code_stack.push(Code::WORD)?;
-                    code_stack.extend(&u32::to_ne_bytes(binding))?;
-                    stack_len += 1;
+                    let binding = self.vm.memory.bind_word_in(self.context, symbol, false)?;
+                    if let Some(arity) = self.known_arity(binding, defined_funcs)? {
+                        let defer = Defer::new(Call::Func(binding), stack_len, arity, arity);
+                        defer_stack.push(defer)?;
+                    } else {
+                        code_stack.push(Code::WORD)?;
+                        code_stack.extend(&u32::to_le_bytes(binding))?;
+                        stack_len += 1;
+                    }
                 }
                 Value::SET_WORD => {
                     let symbol = value.data();
-                    let word_address = self.vm.memory.bind_word(symbol, true)?;
+                    let word_address = self.vm.memory.bind_word_in(self.context, symbol, true)?;
+                    let arity = match self.func_definition_arity(ip, end)? {
+                        Some(arity) => Some(arity),
+                        None => self.func_alias_arity(ip, end, defined_funcs)?,
+                    };
+                    if let Some(arity) = arity {
+                        defined_funcs.push((word_address, arity))?;
+                    }
                     let defer = Defer::new(Call::SetWord(word_address), stack_len, 1, 1);
                     defer_stack.push(defer)?;
                 }
@@ -396,7 +719,7 @@ impl<'a> Process<'a> {
                     let arity = native_func.arity();
                     let consume = native_func.consume();
                     let defer = Defer::new(
-                        Call::CallNative(native_func.func_id()),
+                        Call::Native(native_func.func_id()),
                         stack_len,
                         arity,
                         consume,
@@ -404,15 +727,58 @@ impl<'a> Process<'a> {
                     defer_stack.push(defer)?;
                 }
                 Value::FUNC => {
-                    let func_address = value.data();
-                    let func = self.vm.memory.get::<Func>(func_address)?;
+                    let func = self.vm.memory.get::<Func>(value.data())?;
                     let arity = func.arity();
-                    let defer = Defer::new(Call::CallFunc(func_address), stack_len, arity, arity);
+                    let defer = Defer::new(Call::Func(ip), stack_len, arity, arity);
                     defer_stack.push(defer)?;
                 }
+                Value::GET_WORD => {
+                    let symbol = value.data();
+                    let binding = self.vm.memory.bind_word_in(self.context, symbol, false)?;
+                    code_stack.push(Code::GET_WORD)?;
+                    code_stack.extend(&u32::to_le_bytes(binding))?;
+                    stack_len += 1;
+                }
+                // a lit-word or a lit-path evaluates to a word or a path
+                Value::LIT_WORD => {
+                    compile_const(code_stack, Value::new(Value::WORD, value.data()))?;
+                    stack_len += 1;
+                }
+                Value::LIT_PATH => {
+                    let path = Value::new(Value::PATH, value.data()).with_index(value.index())?;
+                    compile_const(code_stack, path)?;
+                    stack_len += 1;
+                }
+                // a path from a word selects into the value of the word
+                Value::PATH | Value::GET_PATH if self.is_word_path(value)? => {
+                    let len = self.vm.memory.items_at(value)?.len();
+                    self.compile_path(value, len, code_stack, defined_funcs)?;
+                    stack_len += 1;
+                }
+                // a set-path selects up to its last item, which is then
+                // assigned the value that follows
+                Value::SET_PATH if self.is_word_path(value)? => {
+                    let items = self.vm.memory.items_at(value)?;
+                    let len = items.len();
+                    let key = *items
+                        .last()
+                        .filter(|_| len > 1)
+                        .ok_or(MemoryError::TypeMismatch)?;
+                    self.compile_path(value, len - 1, code_stack, defined_funcs)?;
+                    if key.is_paren() {
+                        self.compile_into(key.as_paren()?, code_stack, defined_funcs)?;
+                    } else {
+                        compile_const(code_stack, key)?;
+                    }
+                    defer_stack.push(Defer::new(Call::SetPath, stack_len, 3, 3))?;
+                    stack_len += 2;
+                }
+                Value::PAREN => {
+                    self.compile_into(value.as_paren()?, code_stack, defined_funcs)?;
+                    stack_len += 1;
+                }
                 _ => {
-                    code_stack.extend(&[Code::CONST, value.kind() as u8])?;
-                    code_stack.extend(&u32::to_ne_bytes(value.data()))?;
+                    compile_const(code_stack, value)?;
                     stack_len += 1;
                 }
             }
@@ -424,10 +790,13 @@ impl<'a> Process<'a> {
             1 => {}
             n => code_stack.extend(&[Code::LEAVE, n as u8])?,
         }
-        code_stack.push(Code::RET)?;
-        self.vm.memory.alloc_items(code_stack.as_slice()?)
+        Ok(())
     }
 
+    /// Returns the compiled code of a block, compiling it on first use.
+    ///
+    /// Words are bound in the current context, so a block is bound to the
+    /// context it is first evaluated in and keeps that binding afterwards.
     pub fn get_binding(&mut self, series: Series<Value>) -> Result<Series<u8>, MemoryError> {
         let block = self.vm.memory.get::<Block>(series.address())?;
         let bindings = block.bindings;
@@ -442,17 +811,94 @@ impl<'a> Process<'a> {
     }
 
     pub fn call(&mut self, code_block: Series<u8>) -> Result<(), VmError> {
-        self.call_stack.push(self.ip)?;
+        self.call_stack.push(Frame {
+            ip: self.ip,
+            func: 0,
+            context: self.context,
+        })?;
         self.ip.jmp(code_block);
         Ok(())
     }
 
+    /// Calls a user-defined function: pops its arguments off the stack into
+    /// the parameter slots of the function context and runs the body within
+    /// that context. Previous slot values are kept in `saved` and restored on
+    /// return, so nested and recursive calls each see their own arguments.
+    fn call_func(&mut self, func_address: Address) -> Result<(), VmError> {
+        let func = self.vm.memory.get::<Func>(func_address).copied()?;
+        let context = func.context();
+        let arity = func.arity() as usize;
+        let base = self
+            .stack
+            .len()
+            .checked_sub(arity)
+            .ok_or(MemoryError::StackUnderflow)?;
+        let memory = &mut self.vm.memory;
+        for (i, arg) in self.stack.drain(base)?.iter().enumerate() {
+            let slot = memory.context_slot(context, i as Offset)?;
+            let slot = memory.get_mut::<Value>(slot)?;
+            self.saved.push(*slot)?;
+            *slot = *arg;
+        }
+        self.call_stack.push(Frame {
+            ip: self.ip,
+            func: func_address,
+            context: self.context,
+        })?;
+        self.context = context.address();
+        let code = self.get_binding(func.body())?;
+        self.ip.jmp(code);
+        Ok(())
+    }
+
+    fn leave_func(&mut self, func_address: Address) -> Result<(), VmError> {
+        let func = self.vm.memory.get::<Func>(func_address).copied()?;
+        let context = func.context();
+        for i in (0..func.arity() as Offset).rev() {
+            let slot = self.vm.memory.context_slot(context, i)?;
+            *self.vm.memory.get_mut::<Value>(slot)? = self.saved.pop()?;
+        }
+        Ok(())
+    }
+
+    /// Runs the garbage collector with the stacks and call frames of this
+    /// process as roots, and returns the number of bytes released.
+    pub fn collect_garbage(&mut self) -> Result<Offset, VmError> {
+        let mut roots = Roots::default();
+        for value in self.stack.as_slice()?.iter().chain(self.saved.as_slice()?) {
+            roots.value(*value);
+        }
+        for frame in self.call_stack.as_slice()? {
+            roots.code(frame.ip.0 as Address);
+            roots.context(frame.context);
+            if frame.func != 0 {
+                roots.value(Value::func(frame.func));
+            }
+        }
+        roots.code(self.ip.0 as Address);
+        roots.context(self.context);
+        self.vm.memory.collect_garbage(&roots).map_err(Into::into)
+    }
+
     pub fn exec(&mut self, code_block: Series<u8>) -> Result<Value, VmError> {
         self.call(code_block)?;
         self.run()
     }
 
     pub fn run(&mut self) -> Result<Value, VmError> {
+        self.run_until(0)
+    }
+
+    /// Runs compiled code from within a native function and returns its
+    /// value, leaving the code that called the native to resume afterwards.
+    pub fn eval(&mut self, code_block: Series<u8>) -> Result<Value, VmError> {
+        let depth = self.call_stack.len();
+        self.call(code_block)?;
+        self.run_until(depth)
+    }
+
+    /// Runs until a return leaves `depth` frames on the call stack
+    fn run_until(&mut self, depth: usize) -> Result<Value, VmError> {
         while let Some(op) = self.ip.read_code(&self.vm.memory) {
             match op {
                 Code::CONST => {
@@ -460,6 +906,35 @@ impl<'a> Process<'a> {
                     self.stack
                         .push(Value::new(kind, self.ip.read_u32(&self.vm.memory)?))?;
                 }
+                Code::CONST_VALUE => {
+                    let kind = self.ip.read_u32(&self.vm.memory)?;
+                    let low = self.ip.read_u32(&self.vm.memory)?;
+                    let high = self.ip.read_u32(&self.vm.memory)?;
+                    self.stack.push(Value(kind, low, high))?;
+                }
+                Code::SELECT => {
+                    let kind = self.ip.read_u32(&self.vm.memory)?;
+                    let low = self.ip.read_u32(&self.vm.memory)?;
+                    let high = self.ip.read_u32(&self.vm.memory)?;
+                    let target = self.stack.pop()?;
+                    let result = select(&self.vm.memory, target, Value(kind, low, high))?;
+                    self.stack.push(result)?;
+                }
+                Code::SELECT_KEY => {
+                    let [target, key] = *self.stack.pop_n()?;
+                    let result = select(&self.vm.memory, target, key)?;
+                    self.stack.push(result)?;
+                }
+                Code::GET_WORD => {
+                    let binding = self.ip.read_u32(&self.vm.memory)?;
+                    let value = self.vm.memory.get::<Value>(binding).copied()?;
+                    self.stack.push(value)?;
+                }
+                Code::SET_PATH => {
+                    let [target, key, value] = *self.stack.pop_n()?;
+                    assign(&mut self.vm.memory, target, key, value)?;
+                    self.stack.push(value)?;
+                }
                 Code::WORD => {
                     let binding = self.ip.read_u32(&self.vm.memory)?;
                     let value = self.vm.memory.get::<Value>(binding).copied()?;
@@ -467,8 +942,10 @@ impl<'a> Process<'a> {
                     if value.is_func() {
                         let func_address = value.data();
                         let func = self.vm.memory.get::<Func>(func_address)?;
-                        let body = func.body();
-                        self.call(Series::new(body))?;
+                        if func.arity() != 0 {
+                            return Err(VmError::UnknownArity);
+                        }
+                        self.call_func(func_address)?;
                     } else {
                         self.stack.push(value)?;
                     }
@@ -488,8 +965,13 @@ impl<'a> Process<'a> {
                     self.stack.nip(drop)?;
                 }
                 Code::RET => {
-                    self.ip = self.call_stack.pop()?;
-                    if self.ip.is_halted() {
+                    let frame = self.call_stack.pop()?;
+                    if frame.func != 0 {
+                        self.leave_func(frame.func)?;
+                    }
+                    self.ip = frame.ip;
+                    self.context = frame.context;
+                    if self.ip.is_halted() || self.call_stack.len() == depth {
                         break;
                     }
                 }
@@ -503,6 +985,11 @@ impl<'a> Process<'a> {
                         .ok_or(VmError::BadNativeFunctionIndex)?;
                     native_func(self)?;
                 }
+                Code::CALL_FUNC => {
+                    let slot = self.ip.read_u32(&self.vm.memory)?;
+                    let value = self.vm.memory.get::<Value>(slot).copied()?;
+                    self.call_func(value.as_func()?)?;
+                }
                 _ => {
                     return Err(VmError::InvalidCode);
                 }
@@ -512,12 +999,25 @@ impl<'a> Process<'a> {
     }
 }
 
+impl Drop for Process<'_> {
+    /// Stops accounting allocations to the process. Its allocations stay
+    /// accounted to it until they are collected.
+    fn drop(&mut self) {
+        self.vm.memory.set_quota(self.owner, None);
+        self.vm.memory.set_owner(self.previous_owner);
+    }
+}
+
 //
 
 struct ParseCollector<'a> {
     memory: &'a mut Memory,
     stack: ArrayStack<Value, 256>,
     pos_stack: ArrayStack<usize, 256>,
+    /// Spans of the values on the stack, kept in lockstep with `stack` when
+    /// collecting a source map
+    spans: Vec<Range<usize>>,
+    source_map: Option<SourceMap>,
 }
 
 impl<'a> ParseCollector<'a> {
@@ -526,17 +1026,39 @@ impl<'a> ParseCollector<'a> {
             memory,
             stack: ArrayStack::new(),
             pos_stack: ArrayStack::new(),
+            spans: Vec::new(),
+            source_map: None,
         }
     }
 
+    /// Pushes a value with an empty span, set by the following `span` call.
+    fn push(&mut self, value: Value) -> Result<(), MemoryError> {
+        self.stack.push(value)?;
+        if self.source_map.is_some() {
+            self.spans.push(0..0);
+        }
+        Ok(())
+    }
+
     fn begin(&mut self) -> Result<(), MemoryError> {
         self.pos_stack.push(self.stack.len)
     }
 
     fn end(&mut self, kind: Type) -> Result<(), MemoryError> {
         let pos = self.pos_stack.pop()?;
+        if self.source_map.is_some() {
+            debug_assert_eq!(self.spans.len(), self.stack.len);
+        }
         let block = self.memory.alloc_items(self.stack.drain(pos)?)?;
-        self.stack.push(Value::new(kind, block.address()))
+        if let Some(source_map) = &mut self.source_map {
+            let items = self.spans.drain(pos..);
+            for (index, span) in items.enumerate() {
+                source_map
+                    .spans
+                    .insert((block.address(), index as Offset), span);
+            }
+        }
+        self.push(Value::new(kind, block.address()))
     }
 }
 
@@ -546,23 +1068,82 @@ impl Collector for ParseCollector<'_> {
     /// Called when a string is parsed
     fn string(&mut self, string: &str) -> Result<(), Self::Error> {
         let string = self.memory.alloc_string(string).map(Value::string)?;
-        self.stack.push(string)
+        self.push(string)
     }
 
     /// Called when a word is parsed
     fn word(&mut self, kind: WordKind, symbol: &str) -> Result<(), Self::Error> {
         let symbol = self.memory.get_or_add_symbol(symbol)?;
-        self.stack.push(Value::any_word(kind, symbol))
+        self.push(Value::any_word(kind, symbol))
     }
 
     /// Called when an integer is parsed
-    fn integer(&mut self, value: i32) -> Result<(), Self::Error> {
-        self.stack.push(Value::int(value))
+    fn integer(&mut self, value: i64) -> Result<(), Self::Error> {
+        self.push(Value::int(value))
     }
 
     /// Called when a float is parsed
-    fn float(&mut self, value: f32) -> Result<(), Self::Error> {
-        self.stack.push(Value::float(value))
+    fn float(&mut self, value: f64) -> Result<(), Self::Error> {
+        self.push(Value::float(value))
+    }
+
+    /// Called when a money amount is parsed
+    fn money(&mut self, value: Money) -> Result<(), Self::Error> {
+        self.push(Value::money(value))
+    }
+
+    /// Called when a binary is parsed
+    fn binary(&mut self, bytes: &[u8]) -> Result<(), Self::Error> {
+        let binary = self.memory.alloc_items(bytes).map(Value::binary)?;
+        self.push(binary)
+    }
+
+    /// Called when a date is parsed
+    fn date(&mut self, value: Date) -> Result<(), Self::Error> {
+        self.push(Value::date(value))
+    }
+
+    /// Called when a time is parsed
+    fn time(&mut self, value: Time) -> Result<(), Self::Error> {
+        self.push(Value::time(value))
+    }
+
+    /// Called when a tuple is parsed
+    fn tuple(&mut self, value: Tuple) -> Result<(), Self::Error> {
+        self.push(Value::tuple(value))
+    }
+
+    /// Called when a pair is parsed
+    fn pair(&mut self, value: Pair) -> Result<(), Self::Error> {
+        self.push(Value::pair(value))
+    }
+
+    /// Called when a file is parsed
+    fn file(&mut self, path: &str) -> Result<(), Self::Error> {
+        let file = self.memory.alloc_string(path).map(Value::file)?;
+        self.push(file)
+    }
+
+    /// Called when a URL is parsed
+    fn url(&mut self, url: &str) -> Result<(), Self::Error> {
+        let url = self.memory.alloc_string(url).map(Value::url)?;
+        self.push(url)
+    }
+
+    /// Called when an email is parsed
+    fn email(&mut self, email: &str) -> Result<(), Self::Error> {
+        let email = self.memory.alloc_string(email).map(Value::email)?;
+        self.push(email)
+    }
+
+    /// Called when an issue is parsed
+    fn issue(&mut self, issue: &str) -> Result<(), Self::Error> {
+        let issue = self.memory.alloc_string(issue).map(Value::issue)?;
+        self.push(issue)
+    }
+
+    fn char(&mut self, value: char) -> Result<(), Self::Error> {
+        self.push(Value::char(value))
     }
 
     /// Called at the start of a block
@@ -581,8 +1162,31 @@ impl Collector for ParseCollector<'_> {
     }
 
     /// Called at the end of a path
-    fn end_path(&mut self) -> Result<(), Self::Error> {
-        self.end(Value::PATH)
+    fn end_path(&mut self, kind: PathKind) -> Result<(), Self::Error> {
+        self.end(match kind {
+            PathKind::Path => Value::PATH,
+            PathKind::SetPath => Value::SET_PATH,
+            PathKind::GetPath => Value::GET_PATH,
+            PathKind::LitPath => Value::LIT_PATH,
+        })
+    }
+
+    /// Called at the start of a paren
+    fn begin_paren(&mut self) -> Result<(), Self::Error> {
+        self.begin()
+    }
+
+    /// Called at the end of a paren
+    fn end_paren(&mut self) -> Result<(), Self::Error> {
+        self.end(Value::PAREN)
+    }
+
+    /// Called with the span of the value just collected
+    fn span(&mut self, span: Range<usize>) -> Result<(), Self::Error> {
+        if let Some(last) = self.spans.last_mut() {
+            *last = span;
+        }
+        Ok(())
     }
 }
 
@@ -608,9 +1212,9 @@ mod tests {
 
         match block {
             [
-                Value(Value::SET_WORD, x),
-                Value(Value::INT, 5),
-                Value(Value::WORD, y),
+                Value(Value::SET_WORD, x, 0),
+                Value(Value::INT, 5, 0),
+                Value(Value::WORD, y, 0),
             ] => {
                 assert_eq!(x, y, "Expected x to be equal to y");
             }
@@ -644,9 +1248,9 @@ mod tests {
         let values = vm.memory.peek_at(result.as_block()?, 0)?;
         match values {
             [
-                Value(Value::INT, 1),
-                Value(Value::INT, 2),
-                Value(Value::INT, 3),
+                Value(Value::INT, 1, 0),
+                Value(Value::INT, 2, 0),
+                Value(Value::INT, 3, 0),
             ] => {}
             _ => panic!("Unexpected block structure"),
         }
@@ -727,9 +1331,9 @@ mod tests {
         let values = vm.memory.peek_at(result.as_block()?, 0)?;
         match values {
             [
-                Value(Value::WORD, _),
-                Value(Value::SET_WORD, _),
-                Value(Value::GET_WORD, _),
+                Value(Value::WORD, _, 0),
+                Value(Value::SET_WORD, _, 0),
+                Value(Value::GET_WORD, _, 0),
             ] => {}
             _ => panic!("Unexpected block structure"),
         }
@@ -804,6 +1408,42 @@ mod tests {
         let result = vm.parse_block("\"invalid \\z escape\"");
         assert!(result.is_err(), "Should error on invalid escape sequence");
 
+        let result = vm.parse_block("x: 1\ny: 2 ~");
+        match result {
+            Err(VmError::ParserError(error)) => {
+                assert_eq!((error.location.line, error.location.column), (2, 6));
+            }
+            _ => panic!("Should error on `~`"),
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_spans() -> Result<(), VmError> {
+        let mut vm = create_test_vm()?;
+        let input = "x: 1\n  print [\"a\" obj/name]";
+        let (block, source_map) = vm.parse_block_with_spans(input)?;
+        let block = block.as_block()?;
+        let span = |block, index| source_map.span(block, index).map(|span| &input[span]);
+        assert_eq!(span(block, 0), Some("x:"));
+        assert_eq!(span(block, 2), Some("print"));
+        assert_eq!(span(block, 3), Some("[\"a\" obj/name]"));
+        assert_eq!(span(block, 4), None);
+
+        let inner = vm
+            .memory
+            .items_at(vm.memory.items_at(Value::block(block))?[3])?[1];
+        assert_eq!(span(inner.as_path()?, 1), Some("name"));
+        assert_eq!(span(inner.as_path()?, 0), Some("obj"));
+
+        let input = "(a/b [c]) :d/e f";
+        let (block, source_map) = vm.parse_block_with_spans(input)?;
+        let block = block.as_block()?;
+        let span = |index| source_map.span(block, index).map(|span| &input[span]);
+        assert_eq!(span(0), Some("(a/b [c])"));
+        assert_eq!(span(1), Some(":d/e"));
+        assert_eq!(span(2), Some("f"));
         Ok(())
     }
 
@@ -813,7 +1453,7 @@ mod tests {
         let mut vm = create_test_vm()?;
         let block = vm.parse_block("1 2 3")?;
 
-        let mut process = Process::new(&mut vm);
+        let mut process = Process::new(&mut vm)?;
         let code_block = process.compile(block.as_block()?)?;
         let code = process.vm.memory.get_items(code_block)?;
 
@@ -853,7 +1493,7 @@ mod tests {
         let mut vm = create_test_vm()?;
         let block = vm.parse_block("x: 5 x")?;
 
-        let mut process = Process::new(&mut vm);
+        let mut process = Process::new(&mut vm)?;
         let code_block = process.compile(block.as_block()?)?;
         let code = process.vm.memory.get_items(code_block)?;
 
@@ -897,7 +1537,7 @@ mod tests {
         let mut vm = create_test_vm()?;
         let block = vm.parse_block("x: y: z: 42 y")?;
 
-        let mut process = Process::new(&mut vm);
+        let mut process = Process::new(&mut vm)?;
         let code_block = process.compile(block.as_block()?)?;
         let code = process.vm.memory.get_items(code_block)?;
 
@@ -954,7 +1594,7 @@ mod tests {
         let mut vm = create_test_vm()?;
         let block = vm.parse_block("")?;
 
-        let mut process = Process::new(&mut vm);
+        let mut process = Process::new(&mut vm)?;
         let code_block = process.compile(block.as_block()?)?;
         let code = process.vm.memory.get_items(code_block)?;
 
@@ -971,7 +1611,7 @@ mod tests {
         let mut vm = create_test_vm()?;
         let block = vm.parse_block("add 7 8")?;
 
-        let mut process = Process::new(&mut vm);
+        let mut process = Process::new(&mut vm)?;
         let code_block = process.compile(block.as_block()?)?;
         let code = process.vm.memory.get_items(code_block)?;
 
@@ -1005,7 +1645,7 @@ mod tests {
         let mut vm = create_test_vm()?;
         let block = vm.parse_block("1 + 2")?;
 
-        let mut process = Process::new(&mut vm);
+        let mut process = Process::new(&mut vm)?;
         let code_block = process.compile(block.as_block()?)?;
         let code = process.vm.memory.get_items(code_block)?;
 
@@ -1039,7 +1679,7 @@ mod tests {
         let mut vm = create_test_vm()?;
         let block = vm.parse_block("f: func [] [1 + 2]")?;
 
-        let mut process = Process::new(&mut vm);
+        let mut process = Process::new(&mut vm)?;
         let code_block = process.compile(block.as_block()?)?;
         let code = process.vm.memory.get_items(code_block)?;
 
@@ -1077,7 +1717,7 @@ mod tests {
         let mut vm = create_test_vm()?;
         let block = vm.parse_block(input)?;
 
-        let mut process = Process::new(&mut vm);
+        let mut process = Process::new(&mut vm)?;
         let code_block = process.compile(block.as_block()?)?;
 
         let result = process.exec(code_block)?;
@@ -1087,6 +1727,667 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_exec_wide_numbers() -> Result<(), VmError> {
+        run_test_exec("add 3000000000 3000000000", Value::int(6000000000))?;
+        run_test_exec("x: -9223372036854775808 x", Value::int(i64::MIN))?;
+        run_test_exec("x: 2.718281828459045 x", Value::float(std::f64::consts::E))?;
+
+        let mut vm = create_test_vm()?;
+        let block = vm.parse_block("add 9223372036854775807 1")?;
+        let mut process = Process::new(&mut vm)?;
+        let code = process.compile(block.as_block()?)?;
+        assert!(matches!(process.exec(code), Err(VmError::IntegerOverflow)));
+        Ok(())
+    }
+
+    #[test]
+    fn test_exec_money() -> Result<(), VmError> {
+        let money = |s: &str| Value::money(s.parse().unwrap());
+        run_test_exec("add $0.1 $0.2", money("$0.3"))?;
+        run_test_exec("$10 - $0.01", money("$9.99"))?;
+        run_test_exec("-$1.5 + 1", money("-$0.5"))?;
+        run_test_exec("multiply $19.99 3", money("$59.97"))?;
+        run_test_exec("multiply $0.0005 0.5", money("$0.0002"))?;
+        run_test_exec("multiply $100 0.00001", money("$0.001"))?;
+        run_test_exec("divide $10 3", money("$3.3333"))?;
+        run_test_exec("divide $1 0.0000000000001", money("$10000000000000"))?;
+        run_test_exec("$1.5 + 0.25", money("$1.75"))?;
+        run_test_exec("to-money 12.34", money("$12.34"))?;
+        run_test_exec("to-money \"$-0.25\"", money("-$0.25"))?;
+        run_test_exec("divide 7 2", Value::float(3.5))?;
+        run_test_exec("divide 6 -2", Value::int(-3))?;
+        run_test_exec("multiply 1.5 2", Value::float(3.0))?;
+
+        let mut vm = create_test_vm()?;
+        let block = vm.parse_block("multiply $0.0005 0.5")?;
+        let mut process = Process::new(&mut vm)?;
+        process.set_rounding(Rounding::HalfUp);
+        let code = process.compile(block.as_block()?)?;
+        assert_eq!(process.exec(code)?, money("$0.0003"));
+        drop(process);
+
+        for input in ["divide $1 0", "divide 1 0", "divide 1.0 0"] {
+            let block = vm.parse_block(input)?;
+            let mut process = Process::new(&mut vm)?;
+            let code = process.compile(block.as_block()?)?;
+            assert!(matches!(process.exec(code), Err(VmError::DivisionByZero)));
+        }
+        let block = vm.parse_block("divide -9223372036854775808 -1")?;
+        let mut process = Process::new(&mut vm)?;
+        let code = process.compile(block.as_block()?)?;
+        assert!(matches!(process.exec(code), Err(VmError::IntegerOverflow)));
+        drop(process);
+        for input in [
+            "multiply $922337203685477 10",
+            "divide $1 0.000000000000001",
+        ] {
+            let block = vm.parse_block(input)?;
+            let mut process = Process::new(&mut vm)?;
+            let code = process.compile(block.as_block()?)?;
+            assert!(matches!(
+                process.exec(code),
+                Err(VmError::MoneyError(MoneyError::Overflow))
+            ));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_exec_func_args() -> Result<(), VmError> {
+        run_test_exec("f: func [a b] [add a b] f 3 4", Value::int(7))?;
+        run_test_exec("f: func [x] [x + 1] add f 1 f 10", Value::int(13))?;
+        run_test_exec("x: 100 f: func [x] [x + 1] f 5 x", Value::int(100))?;
+        run_test_exec("f: func [a] [a] g: :f g 1", Value::int(1))?;
+        run_test_exec("f: func [a b] [a - b] g: :f h: :g h 5 2", Value::int(3))?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_exec_func_nested() -> Result<(), VmError> {
+        run_test_exec(
+            "inc: func [n] [n + 1] twice: func [n] [inc inc n] twice 40",
+            Value::int(42),
+        )?;
+        run_test_exec(
+            "g: func [a] [add a 1] f: func [a b] [add g a b] f 1 2",
+            Value::int(4),
+        )?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_exec_func_lexical_scope() -> Result<(), VmError> {
+        run_test_exec("x: 1 g: func [] [x] f: func [x] [g] f 5", Value::int(1))?;
+        run_test_exec(
+            "f: func [a] [g: func [b] [add a b] g 10] f 5",
+            Value::int(15),
+        )?;
+        run_test_exec(
+            "a: 1 f: func [a] [either lt a 10 [a] [0]] add f 7 a",
+            Value::int(8),
+        )?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_exec_func_recursive() -> Result<(), VmError> {
+        run_test_exec(
+            "sum: func [n] [either lt n 1 [0] [add n sum add n -1]] sum 10",
+            Value::int(55),
+        )
+    }
+
+    #[test]
+    fn test_collect_garbage() -> Result<(), VmError> {
+        let mut vm = create_test_vm()?;
+        let block = vm.parse_block("f: func [x] [either lt x 5 [[1]] [x + 1]] f 7")?;
+        let mut process = Process::new(&mut vm)?;
+        let code = process.compile(block.as_block()?)?;
+        assert_eq!(process.exec(code)?, Value::int(8));
+
+        assert!(process.collect_garbage()? > 0);
+
+        let mut heap_ends = Vec::new();
+        for _ in 0..6 {
+            let block = process.vm.parse_block("garbage: [1 2 3] f 1")?;
+            let code = process.compile(block.as_block()?)?;
+            let result = process.exec(code)?.as_block()?;
+            assert_eq!(process.vm.memory.get_items(result)?, &[Value::int(1)]);
+            process.collect_garbage()?;
+            let end = process
+                .vm
+                .memory
+                .allocations()?
+                .last()
+                .map_or(0, |a| a.0 + a.1);
+            heap_ends.push(end);
+        }
+        // reused chunks may alternate between runs, but the heap must not grow
+        let warm = heap_ends[..2].iter().max().copied().unwrap_or(0);
+        assert!(
+            heap_ends[2..].iter().all(|end| *end <= warm),
+            "heap should not grow: {heap_ends:?}"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_process_quota() -> Result<(), VmError> {
+        let mut vm = create_test_vm()?;
+        let block = vm.parse_block("f: func [x] [add x 1] f 1")?;
+
+        let mut process = Process::with_quota(&mut vm, 64)?;
+        let result = process
+            .compile(block.as_block()?)
+            .map_err(VmError::from)
+            .and_then(|code| process.exec(code));
+        assert!(matches!(result, Err(VmError::QuotaExceeded)));
+        drop(process);
+
+        let mut process = Process::with_quota(&mut vm, 4096)?;
+        let code = process.compile(block.as_block()?)?;
+        assert_eq!(process.exec(code)?, Value::int(2));
+        assert!(process.allocated() > 0);
+        drop(process);
+
+        // owners are not reused once they run out
+        vm.next_owner = Owner::MAX;
+        assert!(matches!(
+            Process::new(&mut vm),
+            Err(VmError::TooManyProcesses)
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_exec_series_positions() -> Result<(), VmError> {
+        run_test_exec("index? next next [1 2 3]", Value::int(3))?;
+        run_test_exec("length? at [1 2 3] 2", Value::int(2))?;
+        run_test_exec("tail? skip [1 2] 5", Value::bool(true))?;
+        run_test_exec("head? back next [1 2]", Value::bool(true))?;
+        run_test_exec("length? tail \"abc\"", Value::int(0))?;
+
+        // a block holding a series value past its head
+        let mut vm = create_test_vm()?;
+        let inner = vm.memory.alloc_items(&[Value::int(1), Value::int(2)])?;
+        let second = Value::block(inner).with_index(1)?;
+        let block = vm.memory.alloc_items(&[second])?;
+        let mut process = Process::new(&mut vm)?;
+        let code = process.compile(block)?;
+        assert_eq!(process.exec(code)?, second);
+        Ok(())
+    }
+
+    #[test]
+    fn test_exec_series_editing() -> Result<(), VmError> {
+        let cases: &[(&str, &[i64])] = &[
+            ("x: [1 2 3] insert next x [7 8] x", &[1, 7, 8, 2, 3]),
+            ("x: [1 2 3] insert tail x 4 x", &[1, 2, 3, 4]),
+            ("x: [1 2 3] remove next x x", &[1, 3]),
+            ("x: [1 2 3] change next x [9 9 9] x", &[1, 9, 9, 9]),
+            ("x: [1 2 3] reverse next x x", &[1, 3, 2]),
+            ("copy next [1 2 3]", &[2, 3]),
+        ];
+        for (input, expected) in cases {
+            let mut vm = create_test_vm()?;
+            let block = vm.parse_block(input)?;
+            let mut process = Process::new(&mut vm)?;
+            let code = process.compile(block.as_block()?)?;
+            let result = process.exec(code)?.as_block()?;
+            let items = process.memory().get_items(result)?;
+            let expected: Vec<Value> = expected.iter().map(|i| Value::int(*i)).collect();
+            assert_eq!(items, expected, "{input}");
+        }
+
+        run_test_exec("index? insert next [1 2 3] [7 8]", Value::int(4))?;
+        run_test_exec("length? remove \"\u{e9}t\u{e9}\"", Value::int(2))?;
+
+        // string positions count characters
+        run_test_exec("length? \"\u{e9}t\u{e9}\"", Value::int(3))?;
+        run_test_exec("index? tail \"\u{e9}t\u{e9}\"", Value::int(4))?;
+        run_test_exec("length? skip \"\u{e9}t\u{e9}\" 2", Value::int(1))?;
+        run_test_exec("index? back tail \"\u{e9}t\u{e9}\"", Value::int(3))?;
+        run_test_exec(
+            "index? insert next \"\u{e9}t\" \"\u{f1}\u{f1}\"",
+            Value::int(4),
+        )?;
+        let mut vm = create_test_vm()?;
+        let cases = [
+            ("next \"\u{e9}t\u{e9}\"", "t\u{e9}"),
+            ("at \"a\u{f1}b\" 3", "b"),
+            ("head insert next \"a\u{f1}b\" \"x\"", "ax\u{f1}b"),
+            ("s: \"\u{e9}t\u{e9}\" remove next s s", "\u{e9}\u{e9}"),
+            ("copy next \"\u{e9}t\u{e9}\"", "t\u{e9}"),
+            ("copy-part \"\u{e9}t\u{e9}\" 2", "\u{e9}t"),
+            ("head reverse next \"\u{e9}t\u{e9}\"", "\u{e9}\u{e9}t"),
+            ("s: \"\u{e9}t\" s/2: #\"\u{f1}\" s", "\u{e9}\u{f1}"),
+            ("head change \"\u{f1}b\" \"x\"", "xb"),
+            ("head change next \"a\u{f1}b\" \"\u{e9}\"", "a\u{e9}b"),
+            ("head change \"a\u{f1}b\" \"xyz\"", "xyz"),
+            ("head change next \"a\" \"\u{f1}\u{f1}\"", "a\u{f1}\u{f1}"),
+            ("head change \"\u{e9}\u{e9}\" #\"x\"", "x\u{e9}"),
+            ("head insert next next \"a\u{f1}b\" \"x\"", "a\u{f1}xb"),
+            ("append \"a\u{f1}\" \"\u{e9}\"", "a\u{f1}\u{e9}"),
+        ];
+        for (input, expected) in cases {
+            let block = vm.parse_block(input)?;
+            let mut process = Process::new(&mut vm)?;
+            let code = process.compile(block.as_block()?)?;
+            let result = process.exec(code)?;
+            assert_eq!(process.memory().string_at(result)?, expected, "{input}");
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_exec_grown_block() -> Result<(), VmError> {
+        // the block is compiled after its items moved past a forwarding
+        // header
+        let input = "b: [1] append b 2 append b 3 append b 4 append b 5 either 1 < 2 b [0]";
+        let mut vm = create_test_vm()?;
+        let block = vm.parse_block(input)?;
+        let mut process = Process::new(&mut vm)?;
+        let code = process.compile(block.as_block()?)?;
+        assert_eq!(process.exec(code)?, Value::int(5));
+        assert!(process.memory().verify().is_ok());
+        Ok(())
+    }
+
+    #[test]
+    fn test_exec_edited_block() -> Result<(), VmError> {
+        // a block run before it changes runs its new items afterwards
+        let run = "either 1 < 2 x [0]";
+        let cases = [
+            ("x: [1]", "append x 2", 2),
+            ("x: [1 2]", "remove next x", 1),
+            ("x: [1 2]", "change next x 3", 3),
+            ("x: [1 2]", "reverse x", 1),
+            ("x: [2 1]", "sort x", 2),
+            ("x: [1 2]", "x/2: 5", 5),
+        ];
+        for (init, edit, expected) in cases {
+            let input = format!("{init} {run} {edit} {run}");
+            let mut vm = create_test_vm()?;
+            let block = vm.parse_block(&input)?;
+            let mut process = Process::new(&mut vm)?;
+            let code = process.compile(block.as_block()?)?;
+            assert_eq!(process.exec(code)?, Value::int(expected), "{input}");
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_exec_binary() -> Result<(), VmError> {
+        let cases: &[(&str, &[u8])] = &[
+            ("#{DEADBEEF}", &[0xDE, 0xAD, 0xBE, 0xEF]),
+            ("copy-part next #{DEADBEEF} 2", &[0xAD, 0xBE]),
+            ("to-binary \"h\u{e9}\"", &[b'h', 0xC3, 0xA9]),
+            ("x: #{0102} insert tail x 255 x", &[1, 2, 255]),
+            ("x: #{0102} insert x #{FF} x", &[255, 1, 2]),
+            ("reverse 64#{AQID}", &[3, 2, 1]),
+            ("write-int #{} 0 4 -2", &[0xFF, 0xFF, 0xFF, 0xFE]),
+            ("write-int next #{0000} 0 2 258", &[0, 1, 2]),
+        ];
+        for (input, expected) in cases {
+            let mut vm = create_test_vm()?;
+            let block = vm.parse_block(input)?;
+            let mut process = Process::new(&mut vm)?;
+            let code = process.compile(block.as_block()?)?;
+            let result = process.exec(code)?;
+            let bytes = process.memory().get_items(result.as_binary()?)?;
+            assert_eq!(bytes, *expected, "{input}");
+        }
+
+        run_test_exec("read-int #{DEADBEEF} 0 4", Value::int(0xDEADBEEF))?;
+        run_test_exec("read-int #{DEADBEEF} 2 1", Value::int(0xBE))?;
+        run_test_exec("read-int #{FFFFFFFFFFFFFFFF} 0 8", Value::int(-1))?;
+        run_test_exec("length? next #{DEADBEEF}", Value::int(3))?;
+        run_test_exec("#{00ff} = #{00FF}", Value::bool(true))?;
+
+        let mut vm = create_test_vm()?;
+        let block = vm.parse_block("to-string #{68C3A9}")?;
+        let mut process = Process::new(&mut vm)?;
+        let code = process.compile(block.as_block()?)?;
+        let result = process.exec(code)?;
+        assert_eq!(process.memory().string_at(result)?, "h\u{e9}");
+        drop(process);
+
+        let errors = [
+            "to-string #{C3}",
+            "read-int #{DEAD} 1 2",
+            "read-int #{DEAD} 0 3",
+            "write-int #{} 0 1 256",
+            "write-int #{} 1 1 0",
+        ];
+        for input in errors {
+            let block = vm.parse_block(input)?;
+            let mut process = Process::new(&mut vm)?;
+            let code = process.compile(block.as_block()?)?;
+            assert!(process.exec(code).is_err(), "{input}");
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_exec_dates_and_times() -> Result<(), VmError> {
+        let date = |s: &str| Value::date(s.parse().unwrap());
+        let time = |s: &str| Value::time(s.parse().unwrap());
+        run_test_exec("28-Feb-2024 + 1", date("29-Feb-2024"))?;
+        run_test_exec("1-Mar-2024 - 1", date("29-Feb-2024"))?;
+        run_test_exec("2026-10-16 - 2026-01-01", Value::int(288))?;
+        run_test_exec("1-Jan-9999 - 1-Jan-0001", Value::int(3651694))?;
+        run_test_exec("16-Oct-2026/23:00 + 2:00", date("17-Oct-2026/1:00"))?;
+        run_test_exec("16-Oct-2026/10:00+2:00 - 16-Oct-2026/8:00Z", time("0:00"))?;
+        run_test_exec("16-Oct-2026 - 0:30", date("15-Oct-2026/23:30"))?;
+        run_test_exec("10:30 + 0:45:15.5", time("11:15:15.5"))?;
+        run_test_exec("1:00 - 90", time("0:58:30"))?;
+        run_test_exec("multiply 1:30 2", time("3:00"))?;
+        run_test_exec("divide 1:30 3", time("0:30"))?;
+        run_test_exec("divide 1:30 0:45", Value::float(2.0))?;
+        run_test_exec("2026-10-16 < 2026-10-17", Value::bool(true))?;
+        run_test_exec(
+            "16-Oct-2026/10:00+2:00 < 16-Oct-2026/9:00Z",
+            Value::bool(true),
+        )?;
+        run_test_exec("0:59 < 1:00", Value::bool(true))?;
+        run_test_exec("1.5 < 2", Value::bool(true))?;
+        run_test_exec("2026-10-16/0:00Z = 2026-10-16", Value::bool(true))?;
+
+        let fields = "d: 16-Oct-2026/10:30:15.5+2:00";
+        run_test_exec(&format!("{fields} d/year"), Value::int(2026))?;
+        run_test_exec(&format!("{fields} d/month"), Value::int(10))?;
+        run_test_exec(&format!("{fields} d/day"), Value::int(16))?;
+        run_test_exec(&format!("{fields} d/weekday"), Value::int(5))?;
+        run_test_exec(&format!("{fields} d/zone"), time("2:00"))?;
+        run_test_exec(&format!("{fields} d/date"), date("16-Oct-2026"))?;
+        run_test_exec(&format!("{fields} d/time/minute"), Value::int(30))?;
+        run_test_exec(&format!("{fields} d/second"), Value::float(15.5))?;
+        run_test_exec("t: -1:30 t/hour", Value::int(-1))?;
+        run_test_exec("t: 1:30:05 t/second", Value::int(5))?;
+        run_test_exec("b: [x 10 y: 20] b/y", Value::int(20))?;
+        run_test_exec("b: [x 10 y 20] b/z", Value::none())?;
+        run_test_exec("b: [[1 2] [3 4]] b/2/1", Value::int(3))?;
+        run_test_exec("b: [1 2] b/3", Value::none())?;
+        run_test_exec("f: func [d] [d/day + 1] f 2026-10-16", Value::int(17))?;
+
+        let mut vm = create_test_vm()?;
+        for (input, expected) in [
+            (
+                "to-string 16-Oct-2026/10:30:00+2:00",
+                "16-Oct-2026/10:30+2:00",
+            ),
+            ("to-string 2026-10-16", "16-Oct-2026"),
+            ("to-string 10:30:15.5", "10:30:15.5"),
+            ("to-string $7", "$7.00"),
+        ] {
+            let block = vm.parse_block(input)?;
+            let mut process = Process::new(&mut vm)?;
+            let code = process.compile(block.as_block()?)?;
+            let result = process.exec(code)?;
+            assert_eq!(process.memory().string_at(result)?, expected, "{input}");
+        }
+
+        let errors = [
+            "31-Dec-9999 + 1",
+            "2026-10-16 + 2026-10-16",
+            "divide 1:00 0",
+            "multiply 2026-10-16 2",
+            "1:00 < 1",
+            "d: 2026-10-16 d/foo",
+            "t: 1:00 t/year",
+        ];
+        for input in errors {
+            let block = vm.parse_block(input)?;
+            let mut process = Process::new(&mut vm)?;
+            let code = process.compile(block.as_block()?)?;
+            assert!(process.exec(code).is_err(), "{input}");
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_exec_tuples_and_pairs() -> Result<(), VmError> {
+        let tuple = |s: &str| Value::tuple(s.parse().unwrap());
+        let pair = |s: &str| Value::pair(s.parse().unwrap());
+        run_test_exec("1.2.3 + 1", tuple("2.3.4"))?;
+        run_test_exec("1.2.3 + 0.0.0.4", tuple("1.2.3.4"))?;
+        run_test_exec("250.10.0 + 10", tuple("255.20.10"))?;
+        run_test_exec("1.2.3 - 2", tuple("0.0.1"))?;
+        run_test_exec("multiply 10.20.30 1.5", tuple("15.30.45"))?;
+        run_test_exec("divide 10.20.30 4", tuple("2.5.7"))?;
+        run_test_exec("divide 10.20.30 2.4.5", tuple("5.5.6"))?;
+        run_test_exec("10x20 + 1x2", pair("11x22"))?;
+        run_test_exec("10x20 - 5", pair("5x15"))?;
+        run_test_exec("multiply 2 10x20", pair("20x40"))?;
+        run_test_exec("divide 7x-7 2", pair("3x-3"))?;
+        run_test_exec("1.2.3 < 1.10.0", Value::bool(true))?;
+        run_test_exec("1.2.0 = 1.2.0.0", Value::bool(true))?;
+        run_test_exec("1x2 < 2x1", Value::bool(true))?;
+        run_test_exec("p: 640x480 p/y", Value::int(480))?;
+        run_test_exec("v: 1.2.3 v/2", Value::int(2))?;
+        run_test_exec("v: 1.2.3 v/4", Value::none())?;
+
+        let mut vm = create_test_vm()?;
+        for (input, expected) in [
+            ("to-string 192.168.0.1", "192.168.0.1"),
+            ("to-string -5x5", "-5x5"),
+        ] {
+            let block = vm.parse_block(input)?;
+            let mut process = Process::new(&mut vm)?;
+            let code = process.compile(block.as_block()?)?;
+            let result = process.exec(code)?;
+            assert_eq!(process.memory().string_at(result)?, expected, "{input}");
+        }
+
+        let errors = [
+            "divide 1.2.3 0",
+            "divide 1.2.3.4 1.2.3",
+            "divide 1x2 0x1",
+            "2147483647x0 + 1",
+            "10x20 + 5000000000",
+            "1.2.3 + 1x2",
+            "1.2.3 < 1x2",
+            "p: 1x2 p/z",
+        ];
+        for input in errors {
+            let block = vm.parse_block(input)?;
+            let mut process = Process::new(&mut vm)?;
+            let code = process.compile(block.as_block()?)?;
+            assert!(process.exec(code).is_err(), "{input}");
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_exec_files_urls_emails_and_issues() -> Result<(), VmError> {
+        run_test_exec("length? %data/users.csv", Value::int(14))?;
+        run_test_exec("%a.txt = %a.txt", Value::bool(true))?;
+        run_test_exec("%abc = \"abc\"", Value::bool(false))?;
+        run_test_exec("http://host/x = http://host/x", Value::bool(true))?;
+        run_test_exec("index? next #abc-123", Value::int(2))?;
+
+        let mut vm = create_test_vm()?;
+        let cases = [
+            ("%data/users.csv", Value::FILE, "data/users.csv"),
+            ("http://host/x", Value::URL, "http://host/x"),
+            ("a@b.com", Value::EMAIL, "a@b.com"),
+            ("#abc-123", Value::ISSUE, "abc-123"),
+            ("copy skip %data/users.csv 5", Value::FILE, "users.csv"),
+            ("head insert %.txt \"name\"", Value::FILE, "name.txt"),
+            ("reverse #abc", Value::ISSUE, "cba"),
+            ("copy next a@b.com", Value::EMAIL, "@b.com"),
+            ("to-string %a.txt", Value::STRING, "a.txt"),
+        ];
+        for (input, kind, expected) in cases {
+            let block = vm.parse_block(input)?;
+            let mut process = Process::new(&mut vm)?;
+            let code = process.compile(block.as_block()?)?;
+            let result = process.exec(code)?;
+            assert_eq!(result.kind(), kind, "{input}");
+            assert_eq!(process.memory().string_at(result)?, expected, "{input}");
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_exec_parens() -> Result<(), VmError> {
+        run_test_exec("(1 + 2) * 3", Value::int(9))?;
+        run_test_exec("1 + (2 * 3)", Value::int(7))?;
+        run_test_exec("x: 2 (x + 1) * (x + 8)", Value::int(30))?;
+        run_test_exec("()", Value::none())?;
+        run_test_exec("f: func [a] [a * 2] (f (f 3))", Value::int(12))?;
+        run_test_exec("blk: [10 20 30] i: 1 blk/(i + 1)", Value::int(20))?;
+        run_test_exec("blk: [a [x 5]] blk/(2)/x", Value::int(5))?;
+        run_test_exec("length? [1 (2)]", Value::int(2))?;
+        run_test_exec("x: 5 pick compose [a (x)] 2", Value::int(5))?;
+        run_test_exec("length? compose [(1 + 1) ([2 3]) (4)]", Value::int(4))?;
+        run_test_exec("pick compose [(recycle 1)] 1", Value::int(1))?;
+        run_test_exec(
+            "b: compose [x (either 1 < 2 [\"y\"] [\"n\"]) z] (pick b 2) = \"y\"",
+            Value::bool(true),
+        )?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_exec_path_kinds() -> Result<(), VmError> {
+        run_test_exec("'name = 'name", Value::bool(true))?;
+        run_test_exec("length? 'a/b/c", Value::int(3))?;
+        run_test_exec("f: func [] [42] g: :f g", Value::int(42))?;
+        run_test_exec("blk: [1 2 3] blk/2: 20 blk/2", Value::int(20))?;
+        run_test_exec("blk: [1 2 3] blk/2: 20", Value::int(20))?;
+        run_test_exec("blk: [1 [a 2]] blk/2/a: 5 blk/2/a", Value::int(5))?;
+        run_test_exec("blk: [a 1] blk/b: 2 length? blk", Value::int(4))?;
+        run_test_exec("blk: [1 2 3] i: 2 blk/(i + 1): 0 blk/3", Value::int(0))?;
+        run_test_exec("obj: [f 7] :obj/f", Value::int(7))?;
+        run_test_exec("bin: #{0102} bin/1: 255 bin/1", Value::int(255))?;
+
+        let mut vm = create_test_vm()?;
+        let block = vm.parse_block(r#"s: "añb" s/2: #"n" s"#)?;
+        let mut process = Process::new(&mut vm)?;
+        let code = process.compile(block.as_block()?)?;
+        let result = process.exec(code)?;
+        assert_eq!(process.memory().string_at(result)?, "anb");
+        drop(process);
+
+        let block = vm.parse_block("blk: [1] blk/2: 0")?;
+        let mut process = Process::new(&mut vm)?;
+        let code = process.compile(block.as_block()?)?;
+        assert!(matches!(
+            process.exec(code),
+            Err(VmError::MemoryError(MemoryError::OutOfBounds))
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_exec_chars() -> Result<(), VmError> {
+        run_test_exec(r#"#"a""#, Value::char('a'))?;
+        run_test_exec(r#"#"^(41)" = #"A""#, Value::bool(true))?;
+        run_test_exec(r#"#"a" < #"b""#, Value::bool(true))?;
+        run_test_exec(r#"pick "abc" 2"#, Value::char('b'))?;
+        run_test_exec(r#"pick next "añb" 1"#, Value::char('ñ'))?;
+        run_test_exec(r#"pick "abc" 4"#, Value::none())?;
+        run_test_exec(r#"s: "abc" s/3"#, Value::char('c'))?;
+        run_test_exec("pick #{0A0B} 2", Value::int(11))?;
+        run_test_exec("pick [1 2 3] 3", Value::int(3))?;
+        run_test_exec("length? append [1] [2 3]", Value::int(3))?;
+
+        let mut vm = create_test_vm()?;
+        let cases = [
+            (r#"append "ab" #"x""#, "abx"),
+            (r#"s: next "ab" append s #"é" s"#, "bé"),
+            (r#"head insert next "ac" #"b""#, "abc"),
+            (r#"append %a #"/""#, "a/"),
+            (r#"to-string #"^/""#, "\n"),
+        ];
+        for (input, expected) in cases {
+            let block = vm.parse_block(input)?;
+            let mut process = Process::new(&mut vm)?;
+            let code = process.compile(block.as_block()?)?;
+            let result = process.exec(code)?;
+            assert_eq!(process.memory().string_at(result)?, expected, "{input}");
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_exec_structural_equality() -> Result<(), VmError> {
+        run_test_exec("\"abc\" = \"abc\"", Value::bool(true))?;
+        run_test_exec("[1 [2 \"x\"]] = [1.0 [2 \"x\"]]", Value::bool(true))?;
+        run_test_exec("[1 2] <> [1 3]", Value::bool(true))?;
+        run_test_exec("equal? next [0 1] [1]", Value::bool(true))?;
+        run_test_exec(
+            "length? unique [1 2 1.0 \"a\" \"a\" [1] [1]]",
+            Value::int(4),
+        )?;
+
+        let mut vm = create_test_vm()?;
+        let block = vm.parse_block("sort [3 \"b\" 1.5 \"a\" 2]")?;
+        let mut process = Process::new(&mut vm)?;
+        let code = process.compile(block.as_block()?)?;
+        let result = process.exec(code)?.as_block()?;
+        let items = process.memory().get_items(result)?;
+        assert_eq!(
+            items[..3],
+            [Value::float(1.5), Value::int(2), Value::int(3)]
+        );
+        let text: Vec<_> = items[3..]
+            .iter()
+            .map(|item| process.memory().string_at(*item))
+            .collect::<Result<_, _>>()?;
+        assert_eq!(text, ["a", "b"]);
+        drop(process);
+
+        // a comparison that fails stops the sort, leaving the block as it was
+        let broken = Value::new(Value::STRING, 0xFFFF_FF00);
+        let items = [Value::int(2), broken, Value::int(1), broken];
+        let block = vm.memory.alloc_items(&items)?;
+        vm.memory.set_word_str("x", Value::block(block))?;
+        let code = vm.parse_block("sort x")?;
+        let mut process = Process::new(&mut vm)?;
+        let code = process.compile(code.as_block()?)?;
+        assert!(process.exec(code).is_err());
+        assert_eq!(process.memory().get_items(block)?, items);
+        Ok(())
+    }
+
+    #[test]
+    fn test_exec_recycle() -> Result<(), VmError> {
+        let mut vm = create_test_vm()?;
+        let block = vm.parse_block("x: [1 2] f: func [a] [recycle a] f x")?;
+        let mut process = Process::new(&mut vm)?;
+        let code = process.compile(block.as_block()?)?;
+        let result = process.exec(code)?.as_block()?;
+        assert_eq!(
+            process.vm.memory.get_items(result)?,
+            &[Value::int(1), Value::int(2)]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_image_roundtrip() -> Result<(), VmError> {
+        let path = std::env::temp_dir().join(format!("rebel-vm-{}.img", std::process::id()));
+        let mut vm = create_test_vm()?;
+        let block = vm.parse_block("inc: func [x] [x + 1] y: 41 inc 1")?;
+        let mut process = Process::new(&mut vm)?;
+        let code = process.compile(block.as_block()?)?;
+        assert_eq!(process.exec(code)?, Value::int(2));
+        drop(process);
+        vm.save_image(&path)?;
+
+        let mut vm = Vm::load_image(&path)?;
+        std::fs::remove_file(&path).map_err(MemoryError::from)?;
+        assert_eq!(vm.natives.len(), crate::stdlib::NATIVES.len());
+        let block = vm.parse_block("add inc y 0")?;
+        let mut process = Process::new(&mut vm)?;
+        let code = process.compile(block.as_block()?)?;
+        assert_eq!(process.exec(code)?, Value::int(42));
+        Ok(())
+    }
+
     #[test]
     fn test_exec_simple() -> Result<(), VmError> {
         run_test_exec("1 2 3", Value::int(3))?;
@@ -1103,7 +2404,7 @@ mod tests {
             "either 5 < 10 [1 2 3] [24] either 15 < 1 [42] [22 7 + 8]",
             Value::int(15),
         )?;
-        // run_test_exec("f: func [] [add 1 2] f", Value::int(3))?;
+        run_test_exec("f: func [] [add 1 2] f", Value::int(3))?;
 
         match run_test_exec("some_word", Value::VALUE_NONE) {
             Err(VmError::MemoryError(MemoryError::WordNotFound)) => {}